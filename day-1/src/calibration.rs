@@ -0,0 +1,220 @@
+// Advent of Code - Day 1: Trebucket?! Calibration report
+
+use std::fmt;
+use std::io::BufRead;
+
+use crate::matcher::{Match, Matcher};
+use crate::vocabulary::Vocabulary;
+
+/// Token of a line that was recognised as a digit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Token {
+    pub(crate) text: String,
+    /// Byte offset of the first byte of the token
+    pub(crate) start: usize,
+    /// Byte offset just past the last byte of the token
+    pub(crate) end: usize,
+    pub(crate) value: u32,
+}
+
+impl Token {
+    fn new(line: &str, m: Match) -> Self {
+        Token {
+            text: line[m.start..m.end].to_string(),
+            start: m.start,
+            end: m.end,
+            value: m.value,
+        }
+    }
+}
+
+/// Diagnostics for a single calibration line, line numbers start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LineCalibration {
+    pub(crate) line: usize,
+    pub(crate) first: Token,
+    pub(crate) last: Token,
+    pub(crate) value: u64,
+}
+
+impl fmt::Display for LineCalibration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: `{}` at {}..{} and `{}` at {}..{} give {}",
+            self.line,
+            self.first.text,
+            self.first.start,
+            self.first.end,
+            self.last.text,
+            self.last.start,
+            self.last.end,
+            self.value
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CalibrationError {
+    /// The line does not contain a single token of the vocabulary
+    NoDigit { line: usize, content: String },
+    /// The line could not be read, e.g. because it is not valid UTF-8
+    Unreadable { line: usize, reason: String },
+    /// Concatenating the values of the first and last token overflows
+    Overflow { line: usize, first: u32, last: u32 },
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalibrationError::NoDigit { line, content } => {
+                write!(f, "line {line}: no valid digit in `{content}`")
+            }
+            CalibrationError::Unreadable { line, reason } => {
+                write!(f, "line {line}: unreadable ({reason})")
+            }
+            CalibrationError::Overflow { line, first, last } => {
+                write!(f, "line {line}: concatenating {first} and {last} overflows")
+            }
+        }
+    }
+}
+
+/// How lines without a valid digit are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mode {
+    /// Stop at the first invalid line
+    Strict,
+    /// Skip invalid lines and report them
+    Lenient,
+}
+
+/// Per-line outcome of calibrating a document
+#[derive(Debug, Default)]
+pub(crate) struct CalibrationReport {
+    pub(crate) lines: Vec<Result<LineCalibration, CalibrationError>>,
+}
+
+impl CalibrationReport {
+    /// Sum of the calibration values of all valid lines
+    pub(crate) fn sum(&self) -> u64 {
+        self.lines.iter().flatten().map(|line| line.value).sum()
+    }
+
+    /// Lines that were skipped because they are invalid
+    pub(crate) fn errors(&self) -> impl Iterator<Item = &CalibrationError> {
+        self.lines.iter().filter_map(|line| line.as_ref().err())
+    }
+}
+
+/// Calibrate a single line, `line_number` is only used for diagnostics
+pub(crate) fn calibrate_line(
+    matcher: &Matcher,
+    line_number: usize,
+    line: &str,
+) -> Result<LineCalibration, CalibrationError> {
+    let (first, last) = matcher
+        .first_and_last(line)
+        .ok_or_else(|| CalibrationError::NoDigit {
+            line: line_number,
+            content: line.to_string(),
+        })?;
+    let value =
+        crate::calibration_value(first.value, last.value).ok_or(CalibrationError::Overflow {
+            line: line_number,
+            first: first.value,
+            last: last.value,
+        })?;
+    Ok(LineCalibration {
+        line: line_number,
+        value,
+        first: Token::new(line, first),
+        last: Token::new(line, last),
+    })
+}
+
+/// Calibrate every line of a document, recognising the tokens of `vocabulary`
+///
+/// In [`Mode::Strict`] the first invalid line is returned as error, in
+/// [`Mode::Lenient`] invalid lines are recorded in the report instead.
+pub(crate) fn calibrate<R: BufRead>(
+    reader: R,
+    vocabulary: &Vocabulary,
+    mode: Mode,
+) -> Result<CalibrationReport, CalibrationError> {
+    let matcher = Matcher::new(vocabulary);
+    let mut report = CalibrationReport::default();
+    for (idx, line) in reader.lines().enumerate() {
+        let calibration = line
+            .map_err(|err| CalibrationError::Unreadable {
+                line: idx + 1,
+                reason: err.to_string(),
+            })
+            .and_then(|line| calibrate_line(&matcher, idx + 1, &line));
+        match (mode, calibration) {
+            (Mode::Strict, Err(err)) => return Err(err),
+            (_, calibration) => report.lines.push(calibration),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calibration::{calibrate, CalibrationError, Mode};
+    use crate::vocabulary::Vocabulary;
+
+    const NOISY_INPUT: &str = "two1nine
+xyz
+eightwothree";
+
+    #[test]
+    fn strict_stops_at_invalid_line() {
+        let answer = calibrate(NOISY_INPUT.as_bytes(), &Vocabulary::puzzle(), Mode::Strict);
+        assert_eq!(
+            answer.unwrap_err(),
+            CalibrationError::NoDigit {
+                line: 2,
+                content: "xyz".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn lenient_reports_invalid_lines() {
+        let report =
+            calibrate(NOISY_INPUT.as_bytes(), &Vocabulary::puzzle(), Mode::Lenient).unwrap();
+        assert_eq!(report.sum(), 29 + 83);
+        assert_eq!(report.errors().count(), 1);
+        let last_line = report.lines[2].as_ref().unwrap();
+        assert_eq!(last_line.first.text, "eight");
+        assert_eq!((last_line.last.start, last_line.last.end), (7, 12));
+    }
+
+    #[test]
+    fn overflowing_values() {
+        let vocabulary = Vocabulary::parse("big 4000000000\nsmall 7");
+        let report = calibrate("bigxbig\nbigsmall".as_bytes(), &vocabulary, Mode::Lenient).unwrap();
+        assert_eq!(
+            report.errors().collect::<Vec<_>>(),
+            [&CalibrationError::Overflow {
+                line: 1,
+                first: 4_000_000_000,
+                last: 4_000_000_000
+            }]
+        );
+        assert_eq!(report.sum(), 40_000_000_007);
+    }
+
+    #[test]
+    fn numerals_only() {
+        let report = calibrate(
+            NOISY_INPUT.as_bytes(),
+            &Vocabulary::numerals(),
+            Mode::Lenient,
+        )
+        .unwrap();
+        assert_eq!(report.sum(), 11);
+        assert_eq!(report.errors().count(), 2);
+    }
+}
@@ -1,12 +1,26 @@
 // Advent of Code - Day 1: Trebucket?!
 
-use std::collections::HashMap;
+pub mod calibration;
+pub mod matcher;
+pub mod vocabulary;
+
+use std::io::BufRead;
+
+use calibration::{calibrate, Mode};
+use matcher::Matcher;
+use vocabulary::Vocabulary;
 
 const INPUT: &str = include_str!("./input.txt");
 
 fn solve_part_one(input: &str) -> u32 {
-    input
+    solve_part_one_streaming(input.as_bytes())
+}
+
+/// Solve part one line by line from any buffered reader, e.g. stdin
+fn solve_part_one_streaming<R: BufRead>(reader: R) -> u32 {
+    reader
         .lines()
+        .map(|line| line.expect("Input should be readable."))
         .map(|line| {
             line.chars()
                 .find_map(|char| char.to_digit(10))
@@ -21,51 +35,133 @@ fn solve_part_one(input: &str) -> u32 {
         .sum::<u32>()
 }
 
-fn solve_part_two(input: &str) -> u32 {
-    let digits: HashMap<&str, u32> = HashMap::from([
-        ("1", 1),
-        ("2", 2),
-        ("3", 3),
-        ("4", 4),
-        ("5", 5),
-        ("6", 6),
-        ("7", 7),
-        ("8", 8),
-        ("9", 9),
-        ("one", 1),
-        ("two", 2),
-        ("three", 3),
-        ("four", 4),
-        ("five", 5),
-        ("six", 6),
-        ("seven", 7),
-        ("eight", 8),
-        ("nine", 9),
-    ]);
-
-    input
+fn solve_part_two(input: &str) -> u64 {
+    solve_part_two_streaming(input.as_bytes())
+}
+
+/// Solve part two line by line from any buffered reader, e.g. stdin
+fn solve_part_two_streaming<R: BufRead>(reader: R) -> u64 {
+    solve_with_vocabulary(reader, &Vocabulary::puzzle())
+}
+
+/// Combine the first and last digit tokens by concatenating their decimal values,
+/// `None` if the result does not fit in a `u64`
+fn calibration_value(first: u32, last: u32) -> Option<u64> {
+    u64::from(first)
+        .checked_mul(10_u64.pow(last.checked_ilog10().unwrap_or(0) + 1))?
+        .checked_add(u64::from(last))
+}
+
+/// Sum the calibration values of all lines, recognising the tokens of `vocabulary`
+fn solve_with_vocabulary<R: BufRead>(reader: R, vocabulary: &Vocabulary) -> u64 {
+    let matcher = Matcher::new(vocabulary);
+    reader
         .lines()
+        .map(|line| line.expect("Input should be readable."))
         .map(|line| {
-            digits[(0..line.len())
-                .find_map(|i| digits.keys().find(|key| line[i..].starts_with(*key)))
-                .expect("Each line should contain a valid digit.")]
-                * 10
-                + digits[(0..line.len())
-                    .find_map(|i| {
-                        digits
-                            .keys()
-                            .find(|key| line[..(line.len() - i)].ends_with(*key))
-                    })
-                    .expect("Each line should contain a valid digit.")]
+            let (first, last) = matcher
+                .first_and_last(&line)
+                .expect("Each line should contain a valid digit.");
+            calibration_value(first.value, last.value)
+                .expect("Calibration values should fit in 64 bits.")
         })
         .sum()
 }
 
+/// Print the per-line calibration report of a document, exiting with status 1
+/// if a strict audit finds an invalid line
+fn print_report<R: BufRead>(reader: R, vocabulary: &Vocabulary, mode: Mode) {
+    match calibrate(reader, vocabulary, mode) {
+        Ok(report) => {
+            report.lines.iter().for_each(|line| match line {
+                Ok(calibration) => println!("{calibration}"),
+                Err(err) => println!("skipped {err}"),
+            });
+            println!(
+                "Sum of all calibration values: {:?} ({} lines skipped)",
+                report.sum(),
+                report.errors().count()
+            );
+        }
+        Err(err) => {
+            eprintln!("Invalid calibration document, {err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Vocabulary, whether to read stdin and mode of `--report [options]`,
+/// `None` for unknown options
+fn report_options(options: &[&str]) -> Option<(Vocabulary, bool, Mode)> {
+    let (mut vocabulary, mut stdin, mut mode) = (Vocabulary::puzzle(), false, Mode::Strict);
+    let mut options = options.iter();
+    while let Some(&option) = options.next() {
+        match option {
+            "--part" => {
+                vocabulary = match *options.next()? {
+                    "1" => Vocabulary::numerals(),
+                    "2" => Vocabulary::puzzle(),
+                    _ => return None,
+                }
+            }
+            "--vocabulary" => vocabulary = Vocabulary::load(options.next()?),
+            "--lenient" => mode = Mode::Lenient,
+            "-" => stdin = true,
+            _ => return None,
+        }
+    }
+    Some((vocabulary, stdin, mode))
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: day-1 [- <1|2>] | --vocabulary <language|file> [-] \
+         | --report [--part <1|2> | --vocabulary <language|file>] [-] [--lenient]"
+    );
+    std::process::exit(2);
+}
+
 fn main() {
-    let part_one_answer: u32 = solve_part_one(INPUT);
-    println!("Part one:\nSum of all calibration values: {part_one_answer:?}");
-    let part_two_answer: u32 = solve_part_two(INPUT);
-    println!("Part two:\nSum of all calibration values: {part_two_answer:?}");
+    // `day-1 - <part>` solves a single part streaming from stdin,
+    // `day-1 --vocabulary <language|file> [-]` solves part two with another vocabulary and
+    // `day-1 --report [--part <1|2> | --vocabulary <language|file>] [-] [--lenient]` prints
+    // the per-line diagnostics of a part, by default part two
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer: u32 = solve_part_one(INPUT);
+            println!("Part one:\nSum of all calibration values: {part_one_answer:?}");
+            let part_two_answer: u64 = solve_part_two(INPUT);
+            println!("Part two:\nSum of all calibration values: {part_two_answer:?}");
+        }
+        ["-", "1"] => {
+            let part_one_answer = solve_part_one_streaming(std::io::stdin().lock());
+            println!("Part one:\nSum of all calibration values: {part_one_answer:?}");
+        }
+        ["-", "2"] => {
+            let part_two_answer = solve_part_two_streaming(std::io::stdin().lock());
+            println!("Part two:\nSum of all calibration values: {part_two_answer:?}");
+        }
+        ["--vocabulary", source] => {
+            let answer = solve_with_vocabulary(INPUT.as_bytes(), &Vocabulary::load(source));
+            println!("Sum of all calibration values: {answer:?}");
+        }
+        ["--vocabulary", source, "-"] => {
+            let answer = solve_with_vocabulary(std::io::stdin().lock(), &Vocabulary::load(source));
+            println!("Sum of all calibration values: {answer:?}");
+        }
+        ["--report", ref options @ ..] => {
+            let Some((vocabulary, stdin, mode)) = report_options(options) else {
+                usage()
+            };
+            if stdin {
+                print_report(std::io::stdin().lock(), &vocabulary, mode);
+            } else {
+                print_report(INPUT.as_bytes(), &vocabulary, mode);
+            }
+        }
+        _ => usage(),
+    }
 }
 
 #[cfg(test)]
@@ -92,4 +188,23 @@ mod tests {
         let answer = crate::solve_part_two(example_input);
         assert_eq!(answer, 281);
     }
+
+    #[test]
+    fn part2_dutch() {
+        let example_input = "twee1negen
+        achtweedrie
+        abceen2driexyz";
+        let vocabulary = crate::Vocabulary::builtin("nl").unwrap();
+        let answer = crate::solve_with_vocabulary(example_input.as_bytes(), &vocabulary);
+        assert_eq!(answer, 29 + 83 + 13);
+    }
+
+    #[test]
+    fn part2_multi_digit_words() {
+        let example_input = "tenxthree
+        onexten";
+        let vocabulary = crate::Vocabulary::builtin("en").unwrap();
+        let answer = crate::solve_with_vocabulary(example_input.as_bytes(), &vocabulary);
+        assert_eq!(answer, 103 + 110);
+    }
 }
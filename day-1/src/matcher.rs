@@ -0,0 +1,148 @@
+// Advent of Code - Day 1: Trebucket?! Aho-Corasick digit matcher
+
+use std::collections::VecDeque;
+
+use crate::vocabulary::Vocabulary;
+
+/// Occurrence of a vocabulary token in a line, `start..end` are byte offsets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Match {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) value: u32,
+}
+
+/// Aho-Corasick automaton over all tokens of a vocabulary
+///
+/// The automaton is a complete DFA over bytes, so every byte of a line is
+/// consumed with a single table lookup. Overlapping tokens such as the `eight`
+/// and `two` in `eightwo` are all reported through the dictionary links.
+pub(crate) struct Matcher {
+    transitions: Vec<[usize; 256]>,
+    /// Token ending exactly at this state, as `(length, value)`
+    token: Vec<Option<(usize, u32)>>,
+    /// Nearest state on the failure chain that ends a token
+    dictionary_link: Vec<Option<usize>>,
+}
+
+impl Matcher {
+    pub(crate) fn new(vocabulary: &Vocabulary) -> Self {
+        // Build the trie, the root (0) is never a child so 0 marks a missing edge
+        let mut transitions = vec![[0; 256]];
+        let mut token = vec![None];
+        for (word, value) in &vocabulary.words {
+            let mut state = 0;
+            for &byte in word.as_bytes() {
+                if transitions[state][byte as usize] == 0 {
+                    transitions.push([0; 256]);
+                    token.push(None);
+                    transitions[state][byte as usize] = transitions.len() - 1;
+                }
+                state = transitions[state][byte as usize];
+            }
+            token[state] = Some((word.len(), *value));
+        }
+
+        // Breadth-first over the trie to add failure transitions
+        let mut failure = vec![0; transitions.len()];
+        let mut dictionary_link = vec![None; transitions.len()];
+        let mut queue: VecDeque<usize> = transitions[0]
+            .iter()
+            .copied()
+            .filter(|&child| child != 0)
+            .collect();
+        while let Some(state) = queue.pop_front() {
+            let fallbacks = transitions[failure[state]];
+            for (byte, fallback) in fallbacks.into_iter().enumerate() {
+                let child = transitions[state][byte];
+                if child == 0 {
+                    transitions[state][byte] = fallback;
+                } else {
+                    failure[child] = fallback;
+                    dictionary_link[child] = if token[fallback].is_some() {
+                        Some(fallback)
+                    } else {
+                        dictionary_link[fallback]
+                    };
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        Matcher {
+            transitions,
+            token,
+            dictionary_link,
+        }
+    }
+
+    /// All tokens in `line`, ordered by end offset and longest first
+    pub(crate) fn find_iter<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Match> + 'a {
+        line.bytes()
+            .enumerate()
+            .scan(0, |state, (idx, byte)| {
+                *state = self.transitions[*state][byte as usize];
+                Some((idx + 1, *state))
+            })
+            .flat_map(move |(end, state)| {
+                let first = if self.token[state].is_some() {
+                    Some(state)
+                } else {
+                    self.dictionary_link[state]
+                };
+                std::iter::successors(first, |&s| self.dictionary_link[s]).map(move |s| {
+                    let (len, value) = self.token[s].unwrap();
+                    Match {
+                        start: end - len,
+                        end,
+                        value,
+                    }
+                })
+            })
+    }
+
+    /// First and last token of `line` in a single forward pass
+    ///
+    /// The first token is the one starting leftmost, the last token the one
+    /// ending rightmost; ties are broken in favour of the longer token.
+    pub(crate) fn first_and_last(&self, line: &str) -> Option<(Match, Match)> {
+        self.find_iter(line).fold(None, |acc, m| match acc {
+            None => Some((m, m)),
+            Some((first, last)) => Some((
+                if m.start < first.start || (m.start == first.start && m.end > first.end) {
+                    m
+                } else {
+                    first
+                },
+                if m.end > last.end || (m.end == last.end && m.start < last.start) {
+                    m
+                } else {
+                    last
+                },
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matcher::Matcher;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn overlapping_tokens() {
+        let matcher = Matcher::new(&Vocabulary::puzzle());
+        let values: Vec<u32> = matcher.find_iter("eightwone").map(|m| m.value).collect();
+        assert_eq!(values, vec![8, 2, 1]);
+    }
+
+    #[test]
+    fn longest_token_wins_ties() {
+        let vocabulary = Vocabulary::parse("six 6\nsixteen 16\nteen 0");
+        let (first, last) = Matcher::new(&vocabulary)
+            .first_and_last("xsixteenx")
+            .unwrap();
+        assert_eq!((first.start, first.end, first.value), (1, 8, 16));
+        assert_eq!((last.start, last.end, last.value), (1, 8, 16));
+    }
+}
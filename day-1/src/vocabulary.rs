@@ -0,0 +1,136 @@
+// Advent of Code - Day 1: Trebucket?! Digit vocabularies
+
+const NUMERALS: [(&str, u32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+const ENGLISH: [(&str, u32); 10] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("ten", 10),
+];
+
+const DUTCH: [(&str, u32); 10] = [
+    ("een", 1),
+    ("twee", 2),
+    ("drie", 3),
+    ("vier", 4),
+    ("vijf", 5),
+    ("zes", 6),
+    ("zeven", 7),
+    ("acht", 8),
+    ("negen", 9),
+    ("tien", 10),
+];
+
+const GERMAN: [(&str, u32); 10] = [
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+    ("zehn", 10),
+];
+
+const FRENCH: [(&str, u32); 10] = [
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+    ("dix", 10),
+];
+
+/// Set of tokens that are recognised as digits, together with their value
+#[derive(Debug, Clone)]
+pub(crate) struct Vocabulary {
+    pub(crate) words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// Only the numerals `1` to `9`, as used by part one
+    pub(crate) fn numerals() -> Self {
+        Self::from_words(NUMERALS.iter())
+    }
+
+    /// Numerals and the English words `one` to `nine`, as used by the puzzle
+    pub(crate) fn puzzle() -> Self {
+        Self::from_words(NUMERALS.iter().chain(&ENGLISH[..9]))
+    }
+
+    /// Numerals and the spelled-out numbers up to ten of a built-in language
+    ///
+    /// Supported languages are `en`, `nl`, `de` and `fr`.
+    pub(crate) fn builtin(language: &str) -> Option<Self> {
+        let words: &[(&str, u32)] = match language {
+            "en" => &ENGLISH,
+            "nl" => &DUTCH,
+            "de" => &GERMAN,
+            "fr" => &FRENCH,
+            _ => return None,
+        };
+        Some(Self::from_words(NUMERALS.iter().chain(words)))
+    }
+
+    /// Parse a vocabulary with one `word value` pair per line
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub(crate) fn parse(definition: &str) -> Self {
+        let words = definition
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (word, value) = line
+                    .rsplit_once(char::is_whitespace)
+                    .expect("Vocabulary line should contain a word and a value.");
+                let value = value
+                    .parse::<u32>()
+                    .expect("Vocabulary value should be a number.");
+                (word.trim().to_string(), value)
+            })
+            .collect();
+        Vocabulary { words }
+    }
+
+    /// Load a built-in vocabulary by language code, or otherwise parse the file at `source`
+    pub(crate) fn load(source: &str) -> Self {
+        Self::builtin(source).unwrap_or_else(|| {
+            Self::parse(
+                &std::fs::read_to_string(source)
+                    .expect("Vocabulary should be a built-in language or a readable file."),
+            )
+        })
+    }
+
+    fn from_words<'a>(words: impl Iterator<Item = &'a (&'a str, u32)>) -> Self {
+        Vocabulary {
+            words: words
+                .map(|&(word, value)| (word.to_string(), value))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+// Advent of Code - Day 10: Pipe Maze Enclosed area
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Tile {
+    Loop,
+    Inside,
+    Outside,
+}
+
+impl From<Tile> for char {
+    fn from(tile: Tile) -> Self {
+        match tile {
+            Tile::Loop => '*',
+            Tile::Inside => 'I',
+            Tile::Outside => 'O',
+        }
+    }
+}
+
+/// Render a classification grid with one character per tile
+pub(crate) fn render(grid: &[Vec<Tile>]) -> String {
+    grid.iter()
+        .map(|row| row.iter().map(|&tile| char::from(tile)).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Twice the area enclosed by the ordered `vertices`, by the shoelace formula
+pub(crate) fn double_area(vertices: &[(usize, usize)]) -> u64 {
+    let signed: i64 = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&(r1, c1), &(r2, c2))| r1 as i64 * c2 as i64 - r2 as i64 * c1 as i64)
+        .sum();
+    signed.unsigned_abs()
+}
+
+/// Tiles strictly inside the loop through the ordered tile centres `vertices`
+///
+/// By Pick's theorem `A = i + b / 2 - 1`, where the `b` boundary points are
+/// exactly the loop tiles, so `i = (2A - b) / 2 + 1`.
+pub(crate) fn interior_tiles(vertices: &[(usize, usize)]) -> u64 {
+    (double_area(vertices) + 2 - vertices.len() as u64) / 2
+}
+
+/// Classify every tile by the winding number of the loop around it
+///
+/// The ray from a tile to the right, just below the tile centre, crosses the
+/// loop's vertical steps between this row and the next one. Adding those
+/// steps with their direction from the right gives the winding number of
+/// every tile in the row, and tiles with a non-zero winding number are inside.
+pub(crate) fn winding_grid(
+    rows: usize,
+    columns: usize,
+    vertices: &[(usize, usize)],
+) -> Vec<Vec<Tile>> {
+    let mut grid = vec![vec![Tile::Outside; columns]; rows];
+    let mut crossings = vec![vec![0_i32; columns]; rows];
+    for (&(r1, c1), &(r2, c2)) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+        grid[r1][c1] = Tile::Loop;
+        if c1 == c2 && r1 + 1 == r2 {
+            crossings[r1][c1] += 1;
+        } else if c1 == c2 && r2 + 1 == r1 {
+            crossings[r2][c1] -= 1;
+        }
+    }
+    for (row, crossings) in grid.iter_mut().zip(&crossings) {
+        let mut winding = 0;
+        for (tile, crossing) in row.iter_mut().zip(crossings).rev() {
+            if *tile != Tile::Loop && winding != 0 {
+                *tile = Tile::Inside;
+            }
+            winding += crossing;
+        }
+    }
+    grid
+}
+
+pub(crate) fn solve_part_two_shoelace(input: &str) -> u64 {
+    let (mut graph, starting_position) = crate::part1::parse_pipes(input);
+    let pipe_loop = crate::part1::close_loop(&mut graph, starting_position)
+        .expect("Input should contain a loop through S.");
+    interior_tiles(&pipe_loop)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::area::{render, winding_grid};
+
+    const EXAMPLE_INPUT: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    #[test]
+    fn shoelace_and_pick() {
+        let answer = crate::area::solve_part_two_shoelace(EXAMPLE_INPUT);
+        assert_eq!(answer, 4);
+        let answer = crate::area::solve_part_two_shoelace(
+            "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        );
+        assert_eq!(answer, 10);
+    }
+
+    #[test]
+    fn winding_matches_parity() {
+        let (mut graph, starting_position) = crate::part1::parse_pipes(EXAMPLE_INPUT);
+        let pipe_loop = crate::part1::close_loop(&mut graph, starting_position).unwrap();
+        let winding = winding_grid(graph.len(), graph[0].len(), &pipe_loop);
+        let parity =
+            crate::part2::parity_grid(&graph, &pipe_loop.iter().copied().collect::<HashSet<_>>());
+        assert_eq!(winding, parity);
+        assert_eq!(
+            render(&winding),
+            "OOOOOOOOOO
+O********O
+O********O
+O**OOOO**O
+O**OOOO**O
+O********O
+O*II**II*O
+O********O
+OOOOOOOOOO"
+        );
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let (parity, winding) = crate::classification_grids("S-7\n|.|...\nL-J.");
+        assert_eq!(parity, winding);
+        assert_eq!(render(&parity), "***OOO\n*I*OOO\n***OOO");
+    }
+}
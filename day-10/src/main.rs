@@ -1,13 +1,101 @@
 // Advent of Code - Day 10: Pipe Maze
 
+pub mod area;
 pub mod part1;
 pub mod part2;
+pub mod squeeze;
+
+use std::collections::HashSet;
+
+use area::Tile;
 
 const INPUT: &str = include_str!("./input.txt");
 
+/// Classification grids of the parity scan and of the winding number
+fn classification_grids(input: &str) -> (Vec<Vec<Tile>>, Vec<Vec<Tile>>) {
+    let (mut graph, starting_position) = part1::parse_pipes(input);
+    let pipe_loop = part1::close_loop(&mut graph, starting_position)
+        .expect("Input should contain a loop through S.");
+    let columns = graph.iter().map(Vec::len).max().unwrap_or(0);
+    let parity = part2::parity_grid(&graph, &pipe_loop.iter().copied().collect::<HashSet<_>>());
+    let winding = area::winding_grid(graph.len(), columns, &pipe_loop);
+    (parity, winding)
+}
+
 fn main() {
-    let part_one_answer = part1::solve_part_one(INPUT);
-    println!("Part one: {part_one_answer:?}");
-    let part_two_answer = part2::solve_part_two(INPUT);
-    println!("Part two: {part_two_answer:?}");
+    // `day-10 --shoelace` solves part two with the shoelace formula and Pick's theorem,
+    // `day-10 --map <parity|winding>` prints the loop (*), inside (I) and outside (O)
+    // tiles, `day-10 --compare` lists the tiles on which both methods disagree,
+    // `day-10 --squeeze <scale>` solves part two by flood filling the grid upscaled by a
+    // scale of at least 2 and `day-10 --escape <row> <column>` tells whether an animal
+    // can squeeze out from a tile
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer = part1::solve_part_one(INPUT);
+            println!("Part one: {part_one_answer:?}");
+            let part_two_answer = part2::solve_part_two(INPUT);
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["--shoelace"] => {
+            let part_two_answer = area::solve_part_two_shoelace(INPUT);
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["--map", "parity"] => println!("{}", area::render(&classification_grids(INPUT).0)),
+        ["--map", "winding"] => println!("{}", area::render(&classification_grids(INPUT).1)),
+        ["--compare"] => {
+            let (parity, winding) = classification_grids(INPUT);
+            let mut differences = 0;
+            for (row_idx, (parity, winding)) in parity.iter().zip(&winding).enumerate() {
+                for (column_idx, (parity, winding)) in parity.iter().zip(winding).enumerate() {
+                    if parity != winding {
+                        println!(
+                            "({row_idx}, {column_idx}): parity {parity:?}, winding {winding:?}"
+                        );
+                        differences += 1;
+                    }
+                }
+            }
+            println!("{differences} tiles differ");
+        }
+        ["--squeeze", scale] if scale.parse::<usize>().is_ok_and(|scale| scale >= 2) => {
+            let scale = scale.parse().expect("Scale should be a number.");
+            let squeeze_map = squeeze::squeeze_map(INPUT, scale);
+            println!("{}", squeeze_map.render());
+            let parity_answer = part2::solve_part_two(INPUT) as usize;
+            println!(
+                "Part two: {:?} ({} the parity scan)",
+                squeeze_map.enclosed(),
+                if squeeze_map.enclosed() == parity_answer {
+                    "matches"
+                } else {
+                    "differs from"
+                }
+            );
+        }
+        ["--escape", row, column] => {
+            let row: usize = row.parse().expect("Row should be a number.");
+            let column: usize = column.parse().expect("Column should be a number.");
+            let squeeze_map = squeeze::squeeze_map(INPUT, 2);
+            match squeeze_map
+                .tiles
+                .get(row)
+                .and_then(|tiles| tiles.get(column))
+            {
+                None => eprintln!("({row}, {column}) is not on the map"),
+                Some(Tile::Loop) => println!("({row}, {column}) is part of the loop"),
+                Some(_) if squeeze_map.can_escape(row, column) => {
+                    println!("An animal at ({row}, {column}) can escape")
+                }
+                Some(_) => println!("An animal at ({row}, {column}) is enclosed by the loop"),
+            }
+        }
+        _ => {
+            eprintln!(
+                "Usage: day-10 [--shoelace | --map <parity|winding> | --compare | --squeeze <scale> \
+                 | --escape <row> <column>]"
+            );
+            std::process::exit(2);
+        }
+    }
 }
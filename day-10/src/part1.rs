@@ -1,7 +1,5 @@
 // Advent of Code - Day 10: Pipe Maze Part 1
 
-use std::collections::HashSet;
-
 pub(crate) fn match_pipe(pipe: char) -> [bool; 4] {
     match pipe {
         //     [north, east, south, west]
@@ -35,62 +33,83 @@ pub(crate) fn parse_pipes(input: &str) -> (Vec<Vec<[bool; 4]>>, (usize, usize))
     (graph, starting_position)
 }
 
+/// Position one tile in `direction` from `position`, `None` when that is off the grid
+pub(crate) fn neighbour(
+    graph: &[Vec<[bool; 4]>],
+    (row_idx, column_idx): (usize, usize),
+    direction: usize,
+) -> Option<(usize, usize)> {
+    let position = match direction {
+        0 => (row_idx.checked_sub(1)?, column_idx),
+        1 => (row_idx, column_idx + 1),
+        2 => (row_idx + 1, column_idx),
+        3 => (row_idx, column_idx.checked_sub(1)?),
+        _ => unreachable!(),
+    };
+    graph.get(position.0)?.get(position.1)?;
+    Some(position)
+}
+
+/// Positions of the loop through `starting_position` in the order they are walked
+///
+/// Every step checks that the next pipe connects back, so no pipe is entered
+/// twice and the walk either runs into a dead end or returns to the start.
 pub(crate) fn find_loop(
     graph: &[Vec<[bool; 4]>],
     starting_position: (usize, usize),
-) -> Option<HashSet<(usize, usize)>> {
-    let (mut row_idx, mut column_idx) = starting_position;
-    let mut pipe_loop = HashSet::new();
-    let mut direction = graph[row_idx][column_idx]
-        .iter()
-        .position(|&dir| dir)
-        .unwrap();
+) -> Option<Vec<(usize, usize)>> {
+    let mut position = starting_position;
+    let mut pipe_loop = Vec::new();
+    let mut direction = graph[position.0][position.1].iter().position(|&dir| dir)?;
     loop {
-        if !pipe_loop.insert((row_idx, column_idx)) {
+        pipe_loop.push(position);
+        position = neighbour(graph, position, direction)?;
+        // headed north => coming from the south of the next pipe, etc.
+        let previous_pipe = (direction + 2) % 4;
+        let pipe = graph[position.0][position.1];
+        if !pipe[previous_pipe] {
+            break None;
+        }
+        if position == starting_position {
             break Some(pipe_loop);
         }
-        let previous_pipe = match direction {
-            // north => headed south
-            0 => {
-                row_idx -= 1;
-                2
-            }
-            // east => headed west
-            1 => {
-                column_idx += 1;
-                3
-            }
-            // south => headed north
-            2 => {
-                row_idx += 1;
-                0
-            }
-            // west => headed east
-            3 => {
-                column_idx -= 1;
-                1
+        direction = (0..4).find(|&i| i != previous_pipe && pipe[i])?;
+    }
+}
+
+/// Replace `S` by the pipe that closes the loop through it and return the loop
+///
+/// Only pipes towards neighbours that connect back to `S` are candidates. Junk
+/// pipes next to `S` can connect back as well, so every pair of them is tried.
+pub(crate) fn close_loop(
+    graph: &mut [Vec<[bool; 4]>],
+    starting_position: (usize, usize),
+) -> Option<Vec<(usize, usize)>> {
+    let connected: Vec<usize> = (0..4)
+        .filter(|&direction| {
+            neighbour(graph, starting_position, direction).is_some_and(|(row_idx, column_idx)| {
+                graph[row_idx][column_idx][(direction + 2) % 4]
+            })
+        })
+        .collect();
+    for (idx, &first) in connected.iter().enumerate() {
+        for &second in &connected[idx + 1..] {
+            let mut start_pipe = [false; 4];
+            start_pipe[first] = true;
+            start_pipe[second] = true;
+            graph[starting_position.0][starting_position.1] = start_pipe;
+            if let Some(pipe_loop) = find_loop(graph, starting_position) {
+                return Some(pipe_loop);
             }
-            _ => unreachable!(),
-        };
-        if !graph[row_idx][column_idx][previous_pipe] {
-            break None;
         }
-        direction = (0..4)
-            .find(|&i| i != previous_pipe && graph[row_idx][column_idx][i])
-            .unwrap();
     }
+    None
 }
 
 pub(crate) fn solve_part_one(input: &str) -> u32 {
     let (mut graph, starting_position) = parse_pipes(input);
-    // replace S with all possible pipes and check if a loop exists
-    let pipe_loop = "|-LJ7F"
-        .chars()
-        .find_map(|start_pipe| {
-            graph[starting_position.0][starting_position.1] = match_pipe(start_pipe);
-            find_loop(&graph, starting_position)
-        })
-        .unwrap();
+    let pipe_loop =
+        close_loop(&mut graph, starting_position).expect("Input should contain a loop through S.");
     // farthest position is halfway down the loop
     u32::try_from(pipe_loop.len()).unwrap() / 2
 }
@@ -140,4 +159,13 @@ LJ.LJ";
         let answer = crate::part1::solve_part_one(example_input);
         assert_eq!(answer, 8);
     }
+
+    #[test]
+    fn part1_start_on_border() {
+        let example_input = "S7.
+|L7
+L-J";
+        let answer = crate::part1::solve_part_one(example_input);
+        assert_eq!(answer, 4);
+    }
 }
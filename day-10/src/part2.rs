@@ -1,30 +1,58 @@
 // Advent of Code - Day 10: Pipe Maze Part 2
 
+use std::collections::HashSet;
+
+use crate::area::Tile;
+
+/// Classify every tile by scanning each row and flipping between outside and
+/// inside at every loop pipe that connects to the north
+///
+/// Rows shorter than the widest one are padded with ground, so every row of
+/// the grid has the same length.
+pub(crate) fn parity_grid(
+    graph: &[Vec<[bool; 4]>],
+    pipe_loop: &HashSet<(usize, usize)>,
+) -> Vec<Vec<Tile>> {
+    let columns = graph.iter().map(Vec::len).max().unwrap_or(0);
+    graph
+        .iter()
+        .enumerate()
+        .map(|(row_idx, row)| {
+            // assume topleft of graph is outside of pipe loop
+            let mut inside = false;
+            (0..columns)
+                .map(|column_idx| {
+                    if !pipe_loop.contains(&(row_idx, column_idx)) {
+                        if inside {
+                            Tile::Inside
+                        } else {
+                            Tile::Outside
+                        }
+                    } else {
+                        // flip inside flag when encountering a pipe that connects to north in the pipe loop.
+                        if row[column_idx][0] {
+                            inside = !inside;
+                        }
+                        Tile::Loop
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
 pub(crate) fn solve_part_two(input: &str) -> u32 {
     let (mut graph, starting_position) = crate::part1::parse_pipes(input);
-    // replace S with all possible pipes and check if a loop exists
-    let pipe_loop = "|-LJ7F"
-        .chars()
-        .find_map(|start_pipe| {
-            graph[starting_position.0][starting_position.1] = crate::part1::match_pipe(start_pipe);
-            crate::part1::find_loop(&graph, starting_position)
-        })
-        .unwrap();
-    let mut inside_count = 0;
-    for (row_idx, row) in graph.iter().enumerate() {
-        // assume topleft of graph is outside of pipe loop
-        let mut inside = false;
-        for (column_idx, pipe) in row.iter().enumerate() {
-            // count bits of ground when inside flag is true
-            if !pipe_loop.contains(&(row_idx, column_idx)) {
-                inside_count += u32::from(inside);
-            // flip inside flag when encountering a pipe that connects to north in the pipe loop.
-            } else if pipe[0] {
-                inside = !inside;
-            }
-        }
-    }
-    inside_count
+    let pipe_loop: HashSet<(usize, usize)> =
+        crate::part1::close_loop(&mut graph, starting_position)
+            .expect("Input should contain a loop through S.")
+            .into_iter()
+            .collect();
+    parity_grid(&graph, &pipe_loop)
+        .iter()
+        .flatten()
+        .filter(|&&tile| tile == Tile::Inside)
+        .count() as u32
 }
 
 #[cfg(test)]
@@ -75,20 +103,19 @@ L--J.L7...LJS7F-7L7.
         assert_eq!(answer, 8);
     }
 
-    // Current implementation can't cope with the starting position in the first line.
-    // #[test]
-    // fn part2_junk_pipe_example() {
-    //     let example_input = "FF7FSF7F7F7F7F7F---7
-    // L|LJ||||||||||||F--J
-    // FL-7LJLJ||||||LJL-77
-    // F--JF--7||LJLJ7F7FJ-
-    // L---JF-JLJ.||-FJLJJ7
-    // |F|F-JF---7F7-L7L|7|
-    // |FFJF7L7F-JF7|JL---7
-    // 7-L-JL7||F7|L7F-7F7|
-    // L.L7LFJ|||||FJL7||LJ
-    // L7JLJL-JLJLJL--JLJ.L";
-    //     let answer = crate::part2::solve_part_two(example_input);
-    //     assert_eq!(answer, 10);
-    // }
+    #[test]
+    fn part2_junk_pipe_example() {
+        let example_input = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+        let answer = crate::part2::solve_part_two(example_input);
+        assert_eq!(answer, 10);
+    }
 }
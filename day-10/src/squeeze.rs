@@ -0,0 +1,202 @@
+// Advent of Code - Day 10: Pipe Maze Squeezing between pipes
+
+use crate::area::Tile;
+
+/// Tiles an animal can reach from outside the grid, squeezing between pipes
+///
+/// Every tile becomes a `scale` by `scale` block of cells, in which the loop
+/// pipe blocks the centre cell and the arms from it to the edges of the block
+/// towards its connections. The
+/// cells between two pipes that don't connect stay free, so a flood fill over
+/// the free cells squeezes through every gap, and starting it from a free ring
+/// around the grid finds everything connected to the outside.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SqueezeMap {
+    pub(crate) scale: usize,
+    /// Cells covered by the loop, including the ring around the grid
+    pub(crate) blocked: Vec<Vec<bool>>,
+    /// Free cells reached from outside the grid, including the ring around it
+    pub(crate) reached: Vec<Vec<bool>>,
+    pub(crate) tiles: Vec<Vec<Tile>>,
+}
+
+impl SqueezeMap {
+    pub(crate) fn new(
+        graph: &[Vec<[bool; 4]>],
+        pipe_loop: &[(usize, usize)],
+        scale: usize,
+    ) -> Self {
+        assert!(
+            scale >= 2,
+            "Scale should be at least 2 to leave gaps between pipes."
+        );
+        let rows = graph.len();
+        let columns = graph.iter().map(Vec::len).max().unwrap_or(0);
+        // first cell and centre cell of a tile, shifted by one for the ring around the grid
+        let first = |idx: usize| idx * scale + 1;
+        let centre = |idx: usize| first(idx) + (scale - 1) / 2;
+        let last = |idx: usize| first(idx) + scale - 1;
+        let mut blocked = vec![vec![false; columns * scale + 2]; rows * scale + 2];
+        for &(row_idx, column_idx) in pipe_loop {
+            let (row, column) = (centre(row_idx), centre(column_idx));
+            blocked[row][column] = true;
+            // arms run from the centre up to the edge of the block, so they meet
+            // the arms of the connected pipes whatever the scale
+            let pipe = graph[row_idx][column_idx];
+            if pipe[0] {
+                (first(row_idx)..row).for_each(|row| blocked[row][column] = true);
+            }
+            if pipe[1] {
+                (column + 1..=last(column_idx)).for_each(|column| blocked[row][column] = true);
+            }
+            if pipe[2] {
+                (row + 1..=last(row_idx)).for_each(|row| blocked[row][column] = true);
+            }
+            if pipe[3] {
+                (first(column_idx)..column).for_each(|column| blocked[row][column] = true);
+            }
+        }
+
+        let mut reached = vec![vec![false; blocked[0].len()]; blocked.len()];
+        let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+        reached[0][0] = true;
+        while let Some((row, column)) = stack.pop() {
+            let neighbours = [
+                (row.wrapping_sub(1), column),
+                (row, column + 1),
+                (row + 1, column),
+                (row, column.wrapping_sub(1)),
+            ];
+            for (row, column) in neighbours {
+                if blocked
+                    .get(row)
+                    .and_then(|cells| cells.get(column))
+                    .is_some_and(|&blocked| !blocked)
+                    && !reached[row][column]
+                {
+                    reached[row][column] = true;
+                    stack.push((row, column));
+                }
+            }
+        }
+
+        let tiles = (0..rows)
+            .map(|row_idx| {
+                (0..graph[row_idx].len())
+                    .map(|column_idx| {
+                        let (row, column) = (centre(row_idx), centre(column_idx));
+                        if blocked[row][column] {
+                            Tile::Loop
+                        } else if reached[row][column] {
+                            Tile::Outside
+                        } else {
+                            Tile::Inside
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        SqueezeMap {
+            scale,
+            blocked,
+            reached,
+            tiles,
+        }
+    }
+
+    /// Whether an animal on the tile at `(row_idx, column_idx)` can get out of the loop
+    pub(crate) fn can_escape(&self, row_idx: usize, column_idx: usize) -> bool {
+        self.tiles[row_idx][column_idx] == Tile::Outside
+    }
+
+    /// Number of tiles enclosed by the loop
+    pub(crate) fn enclosed(&self) -> usize {
+        self.tiles
+            .iter()
+            .flatten()
+            .filter(|&&tile| tile == Tile::Inside)
+            .count()
+    }
+
+    /// Render the upscaled grid with `#` for the loop, `~` for cells reached
+    /// from outside and `.` for enclosed cells, without the ring around it
+    pub(crate) fn render(&self) -> String {
+        let inner = |cells: &[bool]| cells[1..cells.len() - 1].to_vec();
+        self.blocked[1..self.blocked.len() - 1]
+            .iter()
+            .zip(&self.reached[1..self.reached.len() - 1])
+            .map(|(blocked, reached)| {
+                inner(blocked)
+                    .into_iter()
+                    .zip(inner(reached))
+                    .map(|cell| match cell {
+                        (true, _) => '#',
+                        (false, true) => '~',
+                        (false, false) => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Squeeze map of the loop through `S` in the input
+pub(crate) fn squeeze_map(input: &str, scale: usize) -> SqueezeMap {
+    let (mut graph, starting_position) = crate::part1::parse_pipes(input);
+    let pipe_loop = crate::part1::close_loop(&mut graph, starting_position)
+        .expect("Input should contain a loop through S.");
+    SqueezeMap::new(&graph, &pipe_loop, scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::squeeze::squeeze_map;
+
+    const SQUEEZED_EXAMPLE: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    #[test]
+    fn squeezing_matches_parity() {
+        for scale in [2, 3, 4, 5] {
+            let answer = squeeze_map(SQUEEZED_EXAMPLE, scale).enclosed();
+            assert_eq!(answer, 4);
+        }
+        let answer = squeeze_map(
+            "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+            2,
+        )
+        .enclosed();
+        assert_eq!(answer, 10);
+    }
+
+    #[test]
+    fn escape_by_squeezing() {
+        let squeeze_map = squeeze_map(SQUEEZED_EXAMPLE, 2);
+        assert!(squeeze_map.can_escape(3, 4));
+        assert!(!squeeze_map.can_escape(6, 2));
+        assert!(squeeze_map.can_escape(0, 0));
+    }
+
+    #[test]
+    fn render_upscaled() {
+        let squeeze_map = squeeze_map("S7\nLJ", 2);
+        assert_eq!(squeeze_map.render(), "###~\n#.#~\n###~\n~~~~");
+    }
+}
@@ -5,9 +5,54 @@ pub mod part2;
 
 const INPUT: &str = include_str!("./input.txt");
 
+/// How long the platform is spun in part two
+pub(crate) struct Params {
+    /// Number of spin cycles to run
+    pub(crate) cycles: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            cycles: 1_000_000_000,
+        }
+    }
+}
+
+impl Params {
+    /// Override a parameter with a `key=value` argument
+    fn set(&mut self, param: &str) -> Result<(), String> {
+        let (key, value) = param
+            .split_once('=')
+            .ok_or_else(|| format!("Parameter `{param}` should be formatted as `key=value`"))?;
+        let value = value
+            .parse()
+            .map_err(|_| format!("Value of parameter `{key}` should be a number"))?;
+        match key {
+            "cycles" => self.cycles = value,
+            _ => return Err(format!("Unknown parameter `{key}`")),
+        }
+        Ok(())
+    }
+}
+
 fn main() {
+    // `day-14 [--param cycles=<count>]...`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut params = Params::default();
+    for pair in args.chunks(2) {
+        let result = match pair {
+            [flag, param] if flag == "--param" => params.set(param),
+            _ => Err(format!("Unexpected argument `{}`", pair[0])),
+        };
+        if let Err(err) = result {
+            eprintln!("{err}");
+            eprintln!("Usage: day-14 [--param cycles=<count>]...");
+            std::process::exit(2);
+        }
+    }
     let part_one_answer = part1::solve(INPUT);
     println!("Part one: {part_one_answer:?}");
-    let part_two_answer = part2::solve(INPUT);
+    let part_two_answer = part2::solve(INPUT, &params);
     println!("Part two: {part_two_answer:?}");
 }
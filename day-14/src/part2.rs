@@ -28,16 +28,16 @@ fn rotate_ccw(platform: &[Vec<u8>]) -> Vec<Vec<u8>> {
     rotated_platform
 }
 
-pub(crate) fn solve(input: &str) -> usize {
+pub(crate) fn solve(input: &str, params: &crate::Params) -> usize {
     let mut platform: Vec<Vec<u8>> = input.lines().map(|l| l.as_bytes().to_vec()).collect();
     let mut cache = HashMap::new();
-    for itr in 1..1_000_000_000 {
+    for itr in 1..=params.cycles {
         for _ in 0..4 {
             roll_north(&mut platform);
             platform = rotate_ccw(&platform);
         }
         if let Some(cached_itr) = cache.insert(platform.clone(), itr) {
-            if (1_000_000_000 - itr) % (itr - cached_itr) == 0 {
+            if (params.cycles - itr).is_multiple_of(itr - cached_itr) {
                 break;
             }
         }
@@ -52,9 +52,7 @@ pub(crate) fn solve(input: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn part2() {
-        let example_input = "O....#....
+    const EXAMPLE_INPUT: &str = "O....#....
 O.OO#....#
 .....##...
 OO.#O....O
@@ -64,7 +62,18 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        let answer = crate::part2::solve(example_input);
+
+    #[test]
+    fn part2() {
+        let answer = crate::part2::solve(EXAMPLE_INPUT, &crate::Params::default());
         assert_eq!(answer, 64);
     }
+
+    #[test]
+    fn part2_cycles() {
+        for (cycles, expected) in [(0, 104), (1, 87), (2, 69), (3, 69)] {
+            let answer = crate::part2::solve(EXAMPLE_INPUT, &crate::Params { cycles });
+            assert_eq!(answer, expected);
+        }
+    }
 }
@@ -6,8 +6,26 @@ pub mod part2;
 const INPUT: &str = include_str!("./input.txt");
 
 fn main() {
-    let part_one_answer = part1::solve(INPUT);
-    println!("Part one: {part_one_answer:?}");
-    let part_two_answer = part2::solve(INPUT);
-    println!("Part two: {part_two_answer:?}");
+    // `day-15 - <part>` solves a single part streaming from stdin
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer = part1::solve(INPUT);
+            println!("Part one: {part_one_answer:?}");
+            let part_two_answer = part2::solve(INPUT);
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["-", "1"] => {
+            let part_one_answer = part1::solve_streaming(std::io::stdin().lock());
+            println!("Part one: {part_one_answer:?}");
+        }
+        ["-", "2"] => {
+            let part_two_answer = part2::solve_streaming(std::io::stdin().lock());
+            println!("Part two: {part_two_answer:?}");
+        }
+        _ => {
+            eprintln!("Usage: day-15 [- <1|2>]");
+            std::process::exit(2);
+        }
+    }
 }
@@ -1,5 +1,7 @@
 // Advent of Code - Day 15: Lens Library Part 1
 
+use std::io::BufRead;
+
 /// Apply the HASH algorithm on a label
 pub(crate) fn hash(label: &str) -> usize {
     label
@@ -7,8 +9,22 @@ pub(crate) fn hash(label: &str) -> usize {
         .fold(0, |acc, ascii_code| (acc + ascii_code as usize) * 17 % 256)
 }
 
+/// Read the comma-separated initialization steps one at a time, ignoring newlines
+pub(crate) fn steps<R: BufRead>(reader: R) -> impl Iterator<Item = String> {
+    reader
+        .split(b',')
+        .map(|step| String::from_utf8(step.expect("Input should be readable.")).unwrap())
+        .map(|step| step.trim().to_string())
+        .filter(|step| !step.is_empty())
+}
+
 pub(crate) fn solve(input: &str) -> usize {
-    input.split(',').map(hash).sum()
+    solve_streaming(input.as_bytes())
+}
+
+/// Solve part one step by step from any buffered reader, e.g. stdin
+pub(crate) fn solve_streaming<R: BufRead>(reader: R) -> usize {
+    steps(reader).map(|step| hash(&step)).sum()
 }
 
 #[cfg(test)]
@@ -27,6 +43,13 @@ mod tests {
         assert_eq!(answer, 1320);
     }
 
+    #[test]
+    fn part1_trailing_newline() {
+        let example_input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+        let answer = crate::part1::solve(example_input);
+        assert_eq!(answer, 1320);
+    }
+
     #[test]
     fn part1_step1() {
         let example_input = "rn=1";
@@ -1,22 +1,29 @@
 // Advent of Code - Day 15: Lens Library Part 2
 
-use crate::part1::hash;
+use std::io::BufRead;
+
+use crate::part1::{hash, steps};
 
 pub(crate) fn solve(input: &str) -> usize {
-    let mut boxes: Vec<Vec<(&str, usize)>> = vec![vec![]; 256];
-    for step in input.split(',') {
+    solve_streaming(input.as_bytes())
+}
+
+/// Solve part two step by step from any buffered reader, e.g. stdin
+pub(crate) fn solve_streaming<R: BufRead>(reader: R) -> usize {
+    let mut boxes: Vec<Vec<(String, usize)>> = vec![vec![]; 256];
+    for step in steps(reader) {
         if step.contains('-') {
             let label = &step[..step.len() - 1];
-            boxes[hash(label)].retain(|&(l, _)| l != label);
+            boxes[hash(label)].retain(|(l, _)| l != label);
         } else {
             let (label, focal_length) = {
                 let (l, fl) = step.split_once('=').unwrap();
-                (l, fl.trim().parse::<usize>().unwrap())
+                (l, fl.parse::<usize>().unwrap())
             };
-            if let Some((_, fl)) = boxes[hash(label)].iter_mut().find(|(l, _)| l == &label) {
+            if let Some((_, fl)) = boxes[hash(label)].iter_mut().find(|(l, _)| l == label) {
                 *fl = focal_length;
             } else {
-                boxes[hash(label)].push((label, focal_length));
+                boxes[hash(label)].push((label.to_string(), focal_length));
             }
         }
     }
@@ -5,9 +5,58 @@ pub mod part2;
 
 const INPUT: &str = include_str!("./input.txt");
 
+/// Range of ratings every category of a part can take in part two
+pub(crate) struct Params {
+    /// Lowest possible rating of each category
+    pub(crate) min_rating: usize,
+    /// Highest possible rating of each category
+    pub(crate) max_rating: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            min_rating: 1,
+            max_rating: 4000,
+        }
+    }
+}
+
+impl Params {
+    /// Override a parameter with a `key=value` argument
+    fn set(&mut self, param: &str) -> Result<(), String> {
+        let (key, value) = param
+            .split_once('=')
+            .ok_or_else(|| format!("Parameter `{param}` should be formatted as `key=value`"))?;
+        let value = value
+            .parse()
+            .map_err(|_| format!("Value of parameter `{key}` should be a number"))?;
+        match key {
+            "min_rating" => self.min_rating = value,
+            "max_rating" => self.max_rating = value,
+            _ => return Err(format!("Unknown parameter `{key}`")),
+        }
+        Ok(())
+    }
+}
+
 fn main() {
+    // `day-19 [--param <min_rating|max_rating>=<rating>]...`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut params = Params::default();
+    for pair in args.chunks(2) {
+        let result = match pair {
+            [flag, param] if flag == "--param" => params.set(param),
+            _ => Err(format!("Unexpected argument `{}`", pair[0])),
+        };
+        if let Err(err) = result {
+            eprintln!("{err}");
+            eprintln!("Usage: day-19 [--param <min_rating|max_rating>=<rating>]...");
+            std::process::exit(2);
+        }
+    }
     let part_one_answer = part1::solve(INPUT);
     println!("Part one: {part_one_answer:?}");
-    let part_two_answer = part2::solve(INPUT);
+    let part_two_answer = part2::solve(INPUT, &params);
     println!("Part two: {part_two_answer:?}");
 }
@@ -36,7 +36,7 @@ fn apply_workflows(
     acc
 }
 
-pub(crate) fn solve(input: &str) -> usize {
+pub(crate) fn solve(input: &str, params: &crate::Params) -> usize {
     let workflows = input
         .split_once("\n\n")
         .unwrap()
@@ -52,15 +52,14 @@ pub(crate) fn solve(input: &str) -> usize {
             (name, (rules, final_workflow))
         })
         .collect::<HashMap<&str, (Vec<&str>, &str)>>();
-    let part: [Vec<usize>; 4] = std::array::from_fn(|_| (1..=4000).collect::<Vec<_>>());
+    let part: [Vec<usize>; 4] =
+        std::array::from_fn(|_| (params.min_rating..=params.max_rating).collect::<Vec<_>>());
     apply_workflows(&workflows, part, "in")
 }
 
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn part2() {
-        let example_input = "px{a<2006:qkq,m>2090:A,rfg}
+    const EXAMPLE_INPUT: &str = "px{a<2006:qkq,m>2090:A,rfg}
 pv{a>1716:R,A}
 lnx{m>1548:A,A}
 rfg{s<537:gd,x>2440:R,A}
@@ -77,7 +76,24 @@ hdj{m>838:A,pv}
 {x=2036,m=264,a=79,s=2244}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}";
-        let answer = crate::part2::solve(example_input);
+
+    #[test]
+    fn part2() {
+        let answer = crate::part2::solve(EXAMPLE_INPUT, &crate::Params::default());
         assert_eq!(answer, 167409079868000);
     }
+
+    #[test]
+    fn part2_rating_range() {
+        for (min_rating, max_rating, expected) in
+            [(1, 1, 1), (1, 10, 10_000), (3000, 4000, 1_004_006_004_001)]
+        {
+            let params = crate::Params {
+                min_rating,
+                max_rating,
+            };
+            let answer = crate::part2::solve(EXAMPLE_INPUT, &params);
+            assert_eq!(answer, expected);
+        }
+    }
 }
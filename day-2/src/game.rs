@@ -0,0 +1,121 @@
+// Advent of Code - Day 2: Cube Conundrum Game model
+
+use std::collections::BTreeMap;
+
+/// Number of cubes per colour, either drawn in a set or contained in a bag
+///
+/// Colours that are not present count as zero cubes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct Bag(pub(crate) BTreeMap<String, u32>);
+
+impl Bag {
+    /// The bag of the puzzle: 12 red, 13 green and 14 blue cubes
+    pub(crate) fn puzzle() -> Self {
+        Bag::parse("12 red, 13 green, 14 blue")
+    }
+
+    /// Number of cubes of `colour`
+    pub(crate) fn get(&self, colour: &str) -> u32 {
+        self.0.get(colour).copied().unwrap_or(0)
+    }
+
+    pub(crate) fn set(&mut self, colour: &str, quantity: u32) {
+        self.0.insert(colour.to_string(), quantity);
+    }
+
+    /// Colours with at least one cube
+    pub(crate) fn colours(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .iter()
+            .filter(|&(_, &quantity)| quantity > 0)
+            .map(|(colour, _)| colour.as_str())
+    }
+
+    /// Whether every colour of `set` fits within this bag
+    pub(crate) fn contains(&self, set: &Bag) -> bool {
+        set.0
+            .iter()
+            .all(|(colour, &quantity)| quantity <= self.get(colour))
+    }
+
+    /// Colour-wise maximum of two bags
+    pub(crate) fn union(&self, other: &Bag) -> Bag {
+        let mut union = self.clone();
+        for (colour, &quantity) in &other.0 {
+            let entry = union.0.entry(colour.clone()).or_insert(0);
+            *entry = (*entry).max(quantity);
+        }
+        union
+    }
+
+    /// Product of the number of cubes of each of the given colours
+    pub(crate) fn power<'a>(&self, colours: impl Iterator<Item = &'a str>) -> u32 {
+        colours.map(|colour| self.get(colour)).product()
+    }
+
+    /// Parse a bag from one or more lines formatted like a set, e.g. `12 red, 13 green`
+    ///
+    /// Empty lines and lines starting with `#` are ignored, so this also reads bag config files.
+    pub(crate) fn parse(definition: &str) -> Self {
+        definition
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(count_cubes)
+            .fold(Bag::default(), |bag, set| bag.union(&set))
+    }
+}
+
+/// Count the cubes of each colour in a set like `3 blue, 4 red`
+pub(crate) fn count_cubes(set: &str) -> Bag {
+    let mut bag = Bag::default();
+    let set = set.replace(',', "");
+    let mut parts = set.split_whitespace();
+
+    while let Some(quantity_part) = parts.next() {
+        let quantity = quantity_part
+            .parse::<u32>()
+            .expect("Part should start with a parsable number");
+        let colour = parts
+            .next()
+            .expect("Quantity should be followed by a colour");
+        *bag.0.entry(colour.to_string()).or_insert(0) += quantity;
+    }
+    bag
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Game {
+    pub(crate) id: u32,
+    pub(crate) sets: Vec<Bag>,
+}
+
+impl Game {
+    /// Fewest cubes of each colour that make this game possible
+    pub(crate) fn minimal_bag(&self) -> Bag {
+        self.sets
+            .iter()
+            .fold(Bag::default(), |acc, set| acc.union(set))
+    }
+
+    pub(crate) fn is_possible(&self, bag: &Bag) -> bool {
+        self.sets.iter().all(|set| bag.contains(set))
+    }
+}
+
+impl From<&str> for Game {
+    fn from(line: &str) -> Self {
+        let (game, sets) = line
+            .split_once(": ")
+            .expect("Line should contain `: ` once.");
+        let id = game
+            .trim()
+            .trim_start_matches("Game ")
+            .parse::<u32>()
+            .expect("Game should have a numeric id.");
+        Game {
+            id,
+            sets: sets.split("; ").map(count_cubes).collect(),
+        }
+    }
+}
@@ -1,79 +1,175 @@
 // Advent of Code - Day 2: Cube Conundrum
 
+pub mod game;
+pub mod query;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufRead;
+
+use game::{Bag, Game};
+
 const INPUT: &str = include_str!("./input.txt");
 
-fn count_cubes(set: &str) -> [u32; 3] {
-    let mut bag = [0; 3];
-    let set = set.replace(',', "");
-    let mut parts = set.split_whitespace();
-
-    while let Some(quantity_part) = parts.next() {
-        let quantity = quantity_part
-            .parse::<u32>()
-            .expect("Part should start with a parsable number");
-        match parts.next() {
-            Some("red") => bag[0] += quantity,
-            Some("green") => bag[1] += quantity,
-            Some("blue") => bag[2] += quantity,
-            _ => unreachable!(),
-        }
-    }
-    bag
+fn solve_part_one(input: &str, bag: &Bag) -> u32 {
+    solve_part_one_streaming(input.as_bytes(), bag)
 }
 
-fn solve_part_one(input: &str) -> u32 {
-    input
+/// Solve part one game by game from any buffered reader, e.g. stdin
+fn solve_part_one_streaming<R: BufRead>(reader: R, bag: &Bag) -> u32 {
+    reader
         .lines()
-        .map(|line| {
-            line.split_once(": ")
-                .expect("Line should contain `: ` once.")
-                .1
-        })
-        .map(|sets| {
-            sets.split("; ")
-                .map(count_cubes)
-                .map(|bag| bag[0] <= 12 && bag[1] <= 13 && bag[2] <= 14)
-                .collect::<Vec<bool>>()
-        })
-        .enumerate()
-        .filter_map(|(idx, vec)| {
-            if vec.iter().all(|&val| val) {
-                Some(idx as u32 + 1)
-            } else {
-                None
-            }
-        })
+        .map(|line| Game::from(line.expect("Input should be readable.").as_str()))
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id)
         .sum()
 }
 
 fn solve_part_two(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            line.split_once(": ")
-                .expect("Line should contain `: ` once.")
-                .1
-        })
-        .map(|sets| sets.split("; ").map(count_cubes).collect::<Vec<[u32; 3]>>())
-        .map(|counts| {
-            counts.iter().fold([0; 3], |acc, cubes| {
-                acc.iter()
-                    .zip(cubes.iter())
-                    .map(|(&a, &b)| a.max(b))
-                    .collect::<Vec<u32>>()
-                    .try_into()
-                    .unwrap()
-            })
-        })
-        .map(|fewest_cubes| fewest_cubes.iter().product::<u32>())
+    solve_part_two_streaming(input.as_bytes())
+}
+
+/// Solve part two game by game from any buffered reader, e.g. stdin
+///
+/// The power of the fewest cubes is taken over every colour seen in the
+/// record, so a game that never shows one of them has a power of zero. As all
+/// colours are only known at the end, the powers are summed per set of colours
+/// the games show.
+fn solve_part_two_streaming<R: BufRead>(reader: R) -> u32 {
+    let mut sums: BTreeMap<Vec<String>, u32> = BTreeMap::new();
+    for line in reader.lines() {
+        let game = Game::from(line.expect("Input should be readable.").as_str());
+        let minimal_bag = game.minimal_bag();
+        let colours = minimal_bag.colours().map(str::to_string).collect();
+        *sums.entry(colours).or_default() += minimal_bag.power(minimal_bag.colours());
+    }
+    let seen: BTreeSet<&String> = sums.keys().flatten().collect();
+    sums.iter()
+        .filter(|(colours, _)| colours.len() == seen.len())
+        .map(|(_, sum)| sum)
         .sum()
 }
 
+/// Print the answer to one of the queries of the `query` module for all games
+fn print_query(query: &str, games: &[Game], bag: &Bag) {
+    match query {
+        "possible" => query::possible_games(games, bag).for_each(|id| println!("Game {id}")),
+        "minimal" => query::minimal_bags(games).for_each(|(id, minimal_bag)| {
+            let cubes: Vec<String> = minimal_bag
+                .0
+                .iter()
+                .map(|(colour, quantity)| format!("{quantity} {colour}"))
+                .collect();
+            println!("Game {id}: {}", cubes.join(", "));
+        }),
+        "binding" => games.iter().for_each(|game| {
+            let colours = query::binding_colours(game, bag);
+            println!("Game {}: {}", game.id, colours.join(", "));
+        }),
+        _ => {
+            eprintln!("Unknown query `{query}`, expected `possible`, `minimal` or `binding`");
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Apply a `--bag`, `--bag-file` or `--param` option with its `value` to `bag`
+fn set_bag(bag: &mut Bag, option: &str, value: Option<String>) -> Result<(), String> {
+    let value = value.ok_or_else(|| format!("`{option}` should be followed by a value"))?;
+    match option {
+        "--bag" => *bag = Bag::parse(&value),
+        "--bag-file" => {
+            let definition = std::fs::read_to_string(&value)
+                .map_err(|err| format!("Bag file `{value}` is unreadable: {err}"))?;
+            *bag = Bag::parse(&definition);
+        }
+        _ => {
+            let (colour, quantity) = value.split_once('=').ok_or_else(|| {
+                format!("Parameter `{value}` should be formatted as `colour=count`")
+            })?;
+            let quantity = quantity
+                .parse()
+                .map_err(|_| format!("Count of `{colour}` should be a number"))?;
+            bag.set(colour, quantity);
+        }
+    }
+    Ok(())
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: day-2 [--bag <set>] [--bag-file <file>] [--param colour=count]... [- <1|2> | --query <possible|minimal|binding> | --design <id,...>]"
+    );
+    std::process::exit(2);
+}
+
 fn main() {
-    let part_one_answer = solve_part_one(INPUT);
-    println!("Part one:\nSum of all possible games: {part_one_answer:?}");
-    let part_two_answer: u32 = solve_part_two(INPUT);
-    println!("Part two:\nSum of all calibration values: {part_two_answer:?}");
+    // `day-2 [--bag <set>] [--bag-file <file>] [--param colour=count]... [- <part> | --query <query> | --design <ids>]`,
+    // where `-` solves a single part streaming from stdin
+    let mut bag = Bag::puzzle();
+    let mut args: Vec<String> = Vec::new();
+    let mut raw_args = std::env::args().skip(1);
+    while let Some(arg) = raw_args.next() {
+        match arg.as_str() {
+            "--bag" | "--bag-file" | "--param" => {
+                if let Err(err) = set_bag(&mut bag, &arg, raw_args.next()) {
+                    eprintln!("{err}");
+                    usage();
+                }
+            }
+            _ => args.push(arg),
+        }
+    }
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer = solve_part_one(INPUT, &bag);
+            println!("Part one:\nSum of all possible games: {part_one_answer:?}");
+            let part_two_answer: u32 = solve_part_two(INPUT);
+            println!("Part two:\nSum of all calibration values: {part_two_answer:?}");
+        }
+        ["-", "1"] => {
+            let part_one_answer = solve_part_one_streaming(std::io::stdin().lock(), &bag);
+            println!("Part one:\nSum of all possible games: {part_one_answer:?}");
+        }
+        ["-", "2"] => {
+            let part_two_answer = solve_part_two_streaming(std::io::stdin().lock());
+            println!("Part two:\nSum of all calibration values: {part_two_answer:?}");
+        }
+        ["--design", ids] => {
+            let games: Vec<Game> = INPUT.lines().map(Game::from).collect();
+            let target = ids
+                .split(',')
+                .map(|id| {
+                    id.trim()
+                        .parse::<u32>()
+                        .expect("Game id should be a number.")
+                })
+                .collect();
+            match query::design_bag(&games, &target) {
+                Ok(design) => {
+                    let cubes: Vec<String> = design
+                        .minimal_bag
+                        .0
+                        .iter()
+                        .map(|(colour, quantity)| format!("{quantity} {colour}"))
+                        .collect();
+                    println!("Minimal bag: {}", cubes.join(", "));
+                    design.exclusions.iter().for_each(|(id, limits)| {
+                        let limits: Vec<String> = limits
+                            .iter()
+                            .map(|(colour, max)| format!("at most {max} {colour}"))
+                            .collect();
+                        println!("Game {id} stays impossible with {}", limits.join(" or "));
+                    });
+                }
+                Err(err) => println!("No bag admits exactly these games: {err}"),
+            }
+        }
+        ["--query", query] => {
+            let games: Vec<Game> = INPUT.lines().map(Game::from).collect();
+            print_query(query, &games, &bag);
+        }
+        _ => usage(),
+    }
 }
 
 #[cfg(test)]
@@ -85,10 +181,22 @@ Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        let answer = crate::solve_part_one(example_input);
+        let answer = crate::solve_part_one(example_input, &crate::Bag::puzzle());
         assert_eq!(answer, 8);
     }
 
+    #[test]
+    fn part1_smaller_bag() {
+        let example_input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        let bag = crate::Bag::parse("6 red, 3 green, 4 blue");
+        let answer = crate::solve_part_one(example_input, &bag);
+        assert_eq!(answer, 7);
+    }
+
     #[test]
     fn part2() {
         let example_input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
@@ -99,4 +207,13 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
         let answer = crate::solve_part_two(example_input);
         assert_eq!(answer, 2286);
     }
+
+    #[test]
+    fn part2_missing_colour() {
+        let example_input = "Game 1: 2 red, 3 blue; 1 green
+Game 2: 4 red, 0 green; 5 blue
+Game 3: 1 red, 2 green, 3 blue";
+        let answer = crate::solve_part_two(example_input);
+        assert_eq!(answer, 6 + 6);
+    }
 }
@@ -0,0 +1,180 @@
+// Advent of Code - Day 2: Cube Conundrum Queries
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::game::{Bag, Game};
+
+/// Ids of the games that are possible with `bag`
+pub(crate) fn possible_games<'a>(
+    games: &'a [Game],
+    bag: &'a Bag,
+) -> impl Iterator<Item = u32> + 'a {
+    games
+        .iter()
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id)
+}
+
+/// Fewest cubes of each colour per game
+pub(crate) fn minimal_bags(games: &[Game]) -> impl Iterator<Item = (u32, Bag)> + '_ {
+    games.iter().map(|game| (game.id, game.minimal_bag()))
+}
+
+/// Colours with the least slack between `bag` and the fewest cubes `game` needs
+///
+/// For a possible game these are the colours that would make the game
+/// impossible first when cubes are taken out of the bag, for an impossible
+/// game these are the colours that exceed the bag the most.
+pub(crate) fn binding_colours(game: &Game, bag: &Bag) -> Vec<String> {
+    let minimal_bag = game.minimal_bag();
+    let slack = |colour: &str| bag.get(colour) as i64 - minimal_bag.get(colour) as i64;
+    let Some(least_slack) = minimal_bag.colours().map(slack).min() else {
+        return Vec::new();
+    };
+    minimal_bag
+        .colours()
+        .filter(|colour| slack(colour) == least_slack)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Bag configuration that makes exactly a target set of games possible
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BagDesign {
+    /// The unique minimal bag, every valid bag contains at least these cubes
+    pub(crate) minimal_bag: Bag,
+    /// Per excluded game the `(colour, maximum quantity)` limits of which at
+    /// least one must hold for that game to stay impossible
+    pub(crate) exclusions: Vec<(u32, Vec<(String, u32)>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DesignError {
+    /// A target game id does not occur in the record
+    UnknownGame(u32),
+    /// Game `excluded` needs no more cubes than the target games together,
+    /// so every bag that allows the target games also allows it
+    Unavoidable { excluded: u32 },
+}
+
+impl fmt::Display for DesignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DesignError::UnknownGame(id) => write!(f, "game {id} does not exist"),
+            DesignError::Unavoidable { excluded } => write!(
+                f,
+                "game {excluded} is possible with every bag that allows the target games"
+            ),
+        }
+    }
+}
+
+/// Find the bags for which exactly the games in `target` are possible
+///
+/// A game is possible iff the bag contains its minimal bag, so any valid bag
+/// contains the union of the minimal bags of the target games. That union is
+/// therefore the only minimal candidate: if it already admits an excluded
+/// game, so does every larger bag and no valid configuration exists.
+pub(crate) fn design_bag(games: &[Game], target: &BTreeSet<u32>) -> Result<BagDesign, DesignError> {
+    if let Some(&id) = target
+        .iter()
+        .find(|&&id| !games.iter().any(|game| game.id == id))
+    {
+        return Err(DesignError::UnknownGame(id));
+    }
+    let minimal_bag = games
+        .iter()
+        .filter(|game| target.contains(&game.id))
+        .fold(Bag::default(), |acc, game| acc.union(&game.minimal_bag()));
+    let exclusions = games
+        .iter()
+        .filter(|game| !target.contains(&game.id))
+        .map(|game| {
+            let limits: Vec<(String, u32)> = game
+                .minimal_bag()
+                .0
+                .into_iter()
+                .filter(|(colour, quantity)| minimal_bag.get(colour) < *quantity)
+                .map(|(colour, quantity)| (colour, quantity - 1))
+                .collect();
+            if limits.is_empty() {
+                Err(DesignError::Unavoidable { excluded: game.id })
+            } else {
+                Ok((game.id, limits))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(BagDesign {
+        minimal_bag,
+        exclusions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{Bag, Game};
+    use crate::query::{
+        binding_colours, design_bag, minimal_bags, possible_games, BagDesign, DesignError,
+    };
+
+    const EXAMPLE_INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn other_colours() {
+        let games: Vec<Game> = ["Game 7: 2 purple, 1 red; 3 purple", "Game 8: 1 yellow"]
+            .into_iter()
+            .map(Game::from)
+            .collect();
+        let bag = Bag::parse("3 purple, 1 red");
+        assert_eq!(possible_games(&games, &bag).collect::<Vec<u32>>(), vec![7]);
+        assert_eq!(binding_colours(&games[0], &bag), vec!["purple", "red"]);
+        assert_eq!(binding_colours(&games[1], &bag), vec!["yellow"]);
+    }
+
+    #[test]
+    fn minimal_bag_per_game() {
+        let games: Vec<Game> = EXAMPLE_INPUT.lines().map(Game::from).collect();
+        let (id, bag) = minimal_bags(&games).nth(2).unwrap();
+        assert_eq!(id, 3);
+        assert_eq!(bag, Bag::parse("20 red, 13 green, 6 blue"));
+        assert_eq!(binding_colours(&games[2], &Bag::puzzle()), vec!["red"]);
+    }
+
+    #[test]
+    fn design_for_target_games() {
+        let games: Vec<Game> = EXAMPLE_INPUT.lines().map(Game::from).collect();
+        let design = design_bag(&games, &[1, 2, 5].into()).unwrap();
+        assert_eq!(
+            design,
+            BagDesign {
+                minimal_bag: Bag::parse("6 red, 3 green, 6 blue"),
+                exclusions: vec![
+                    (3, vec![("green".to_string(), 12), ("red".to_string(), 19)]),
+                    (4, vec![("blue".to_string(), 14), ("red".to_string(), 13)]),
+                ],
+            }
+        );
+        assert_eq!(
+            possible_games(&games, &design.minimal_bag).collect::<Vec<u32>>(),
+            vec![1, 2, 5]
+        );
+    }
+
+    #[test]
+    fn design_without_solution() {
+        let games: Vec<Game> = EXAMPLE_INPUT.lines().map(Game::from).collect();
+        assert_eq!(
+            design_bag(&games, &[3, 4].into()),
+            Err(DesignError::Unavoidable { excluded: 1 })
+        );
+        assert_eq!(
+            design_bag(&games, &[9].into()),
+            Err(DesignError::UnknownGame(9))
+        );
+    }
+}
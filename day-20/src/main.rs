@@ -5,8 +5,53 @@ pub mod part2;
 
 const INPUT: &str = include_str!("./input.txt");
 
+/// How often the button is pushed in part one
+pub(crate) struct Params {
+    /// Number of times the button is pushed
+    pub(crate) button_presses: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            button_presses: 1000,
+        }
+    }
+}
+
+impl Params {
+    /// Override a parameter with a `key=value` argument
+    fn set(&mut self, param: &str) -> Result<(), String> {
+        let (key, value) = param
+            .split_once('=')
+            .ok_or_else(|| format!("Parameter `{param}` should be formatted as `key=value`"))?;
+        let value = value
+            .parse()
+            .map_err(|_| format!("Value of parameter `{key}` should be a number"))?;
+        match key {
+            "button_presses" => self.button_presses = value,
+            _ => return Err(format!("Unknown parameter `{key}`")),
+        }
+        Ok(())
+    }
+}
+
 fn main() {
-    let part_one_answer = part1::solve(INPUT);
+    // `day-20 [--param button_presses=<count>]...`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut params = Params::default();
+    for pair in args.chunks(2) {
+        let result = match pair {
+            [flag, param] if flag == "--param" => params.set(param),
+            _ => Err(format!("Unexpected argument `{}`", pair[0])),
+        };
+        if let Err(err) = result {
+            eprintln!("{err}");
+            eprintln!("Usage: day-20 [--param button_presses=<count>]...");
+            std::process::exit(2);
+        }
+    }
+    let part_one_answer = part1::solve(INPUT, &params);
     println!("Part one: {part_one_answer:?}");
     let part_two_answer = part2::solve(INPUT);
     println!("Part two: {part_two_answer:?}");
@@ -8,7 +8,7 @@ pub(crate) enum Module<'a> {
     Broadcaster,
 }
 
-pub(crate) fn solve(input: &str) -> usize {
+pub(crate) fn solve(input: &str, params: &crate::Params) -> usize {
     let mut module_configuration = HashMap::new();
     let mut state = HashMap::new();
     for line in input.lines() {
@@ -34,7 +34,7 @@ pub(crate) fn solve(input: &str) -> usize {
 
     let mut q = VecDeque::new();
     let mut pulses = [0, 0];
-    for _ in 0..1000 {
+    for _ in 0..params.button_presses {
         q.push_back(("broadcaster", "button", false));
         while let Some((module, source, pulse)) = q.pop_front() {
             pulses[pulse as usize] += 1;
@@ -70,10 +70,22 @@ mod tests {
 %b -> c
 %c -> inv
 &inv -> a";
-        let answer = crate::part1::solve(example_input);
+        let answer = crate::part1::solve(example_input, &crate::Params::default());
         assert_eq!(answer, 32000000);
     }
 
+    #[test]
+    fn part1_single_press() {
+        let example_input = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+        let params = crate::Params { button_presses: 1 };
+        let answer = crate::part1::solve(example_input, &params);
+        assert_eq!(answer, 32);
+    }
+
     #[test]
     fn part1_interesting() {
         let example_input = "broadcaster -> a
@@ -81,7 +93,7 @@ mod tests {
 &inv -> b
 %b -> con
 &con -> output";
-        let answer = crate::part1::solve(example_input);
+        let answer = crate::part1::solve(example_input, &crate::Params::default());
         assert_eq!(answer, 11687500);
     }
 }
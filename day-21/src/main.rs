@@ -5,9 +5,58 @@ pub mod part2;
 
 const INPUT: &str = include_str!("./input.txt");
 
+/// Step budgets of the gardener on the original and on the infinite garden
+pub(crate) struct Params {
+    /// Number of steps the gardener takes on the original garden
+    pub(crate) steps: usize,
+    /// Number of steps the gardener takes on the infinitely repeating garden
+    pub(crate) infinite_steps: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            steps: 64,
+            infinite_steps: 26501365,
+        }
+    }
+}
+
+impl Params {
+    /// Override a parameter with a `key=value` argument
+    fn set(&mut self, param: &str) -> Result<(), String> {
+        let (key, value) = param
+            .split_once('=')
+            .ok_or_else(|| format!("Parameter `{param}` should be formatted as `key=value`"))?;
+        let value = value
+            .parse()
+            .map_err(|_| format!("Value of parameter `{key}` should be a number"))?;
+        match key {
+            "steps" => self.steps = value,
+            "infinite_steps" => self.infinite_steps = value,
+            _ => return Err(format!("Unknown parameter `{key}`")),
+        }
+        Ok(())
+    }
+}
+
 fn main() {
-    let part_one_answer = part1::solve(INPUT, 64);
+    // `day-21 [--param <steps|infinite_steps>=<count>]...`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut params = Params::default();
+    for pair in args.chunks(2) {
+        let result = match pair {
+            [flag, param] if flag == "--param" => params.set(param),
+            _ => Err(format!("Unexpected argument `{}`", pair[0])),
+        };
+        if let Err(err) = result {
+            eprintln!("{err}");
+            eprintln!("Usage: day-21 [--param <steps|infinite_steps>=<count>]...");
+            std::process::exit(2);
+        }
+    }
+    let part_one_answer = part1::solve(INPUT, &params);
     println!("Part one: {part_one_answer:?}");
-    let part_two_answer = part2::solve(INPUT);
+    let part_two_answer = part2::solve(INPUT, &params);
     println!("Part two: {part_two_answer:?}");
 }
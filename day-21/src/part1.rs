@@ -47,7 +47,8 @@ fn bfs(
     distances
 }
 
-pub(crate) fn solve(input: &str, steps: usize) -> usize {
+pub(crate) fn solve(input: &str, params: &crate::Params) -> usize {
+    let steps = params.steps;
     let grid: Vec<Vec<char>> = input
         .trim()
         .lines()
@@ -78,7 +79,11 @@ mod tests {
 .##.#.####.
 .##..##.##.
 ...........";
-        let answer = crate::part1::solve(example_input, 6);
+        let params = crate::Params {
+            steps: 6,
+            ..Default::default()
+        };
+        let answer = crate::part1::solve(example_input, &params);
         assert_eq!(answer, 16);
     }
 }
@@ -53,7 +53,16 @@ fn bfs(
     distances
 }
 
-pub(crate) fn solve(input: &str) -> usize {
+/// Number of plots the gardener can end on after exactly `steps` steps
+fn reachable(distances: &HashMap<(isize, isize), usize>, steps: usize) -> usize {
+    let parity = steps % 2;
+    distances
+        .values()
+        .filter(|&&dist| dist <= steps && dist % 2 == parity)
+        .count()
+}
+
+pub(crate) fn solve(input: &str, params: &crate::Params) -> usize {
     // key observations:
     // all edges are reachable from starting position
     // border itself is clear
@@ -68,10 +77,21 @@ pub(crate) fn solve(input: &str) -> usize {
 
     let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
     let grid_size = grid.len(); // grid is assumed to be square for this solution
-    let steps = 26501365;
+    let steps = params.infinite_steps;
     let remaining_steps = steps % grid_size; // 65
     let start_pos = find_start_pos(&grid);
 
+    // The quadratic only fits when the gardener starts in the middle of a square
+    // garden and the steps end exactly on the edge of a garden, otherwise count
+    // the reachable plots directly
+    let half = (grid_size / 2) as isize;
+    if grid.iter().any(|row| row.len() != grid_size)
+        || start_pos != (half, half)
+        || remaining_steps != grid_size / 2
+    {
+        return reachable(&bfs(&grid, start_pos, steps), steps);
+    }
+
     // To interpolate a quadratic curve: f(k) = A k^2 + B k + C, we'll compute 3 data points
     // (k=0, y0), (k=1, y1), (k=2, y2)
     // f(0): C => y0 = C
@@ -83,27 +103,15 @@ pub(crate) fn solve(input: &str) -> usize {
 
     // (0, y0): data point for remaining_steps
     let y0_steps = remaining_steps;
-    let parity = y0_steps % 2;
-    let y0 = distances
-        .values()
-        .filter(|&dist| dist <= &y0_steps && dist % 2 == parity)
-        .count() as isize;
+    let y0 = reachable(&distances, y0_steps) as isize;
 
     // (1, Y_1): data point for single full grid traversal plus remaining_steps
     let y1_steps = grid_size + remaining_steps;
-    let parity = y1_steps % 2;
-    let y1 = distances
-        .values()
-        .filter(|&dist| dist <= &y1_steps && dist % 2 == parity)
-        .count() as isize;
+    let y1 = reachable(&distances, y1_steps) as isize;
 
     // (2, Y_1): data point for two full grid traversals plus remaining_steps
     let y2_steps = 2 * grid_size + remaining_steps;
-    let parity = y2_steps % 2;
-    let y2 = distances
-        .values()
-        .filter(|&dist| dist <= &y2_steps && dist % 2 == parity)
-        .count() as isize;
+    let y2 = reachable(&distances, y2_steps) as isize;
 
     // Solve for A
     // A + B = y1 - y0
@@ -132,3 +140,30 @@ pub(crate) fn solve(input: &str) -> usize {
     let n = ((steps - remaining_steps) / grid_size) as isize;
     (a * (n * n) + b * n + c) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE_INPUT: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn part2() {
+        for (infinite_steps, expected) in [(10, 50), (50, 1594), (100, 6536)] {
+            let params = crate::Params {
+                infinite_steps,
+                ..Default::default()
+            };
+            let answer = crate::part2::solve(EXAMPLE_INPUT, &params);
+            assert_eq!(answer, expected);
+        }
+    }
+}
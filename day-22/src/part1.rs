@@ -7,7 +7,18 @@ fn resting_on(brick: &[usize; 6]) -> Vec<(usize, usize)> {
 }
 
 pub(crate) fn collapse(bricks: Vec<[usize; 6]>) -> Vec<[usize; 6]> {
-    let mut floor = [[0; 10]; 10];
+    // the floor spans every x and y coordinate of the bricks
+    let width = bricks
+        .iter()
+        .map(|brick| brick[0].max(brick[3]) + 1)
+        .max()
+        .unwrap_or(0);
+    let depth = bricks
+        .iter()
+        .map(|brick| brick[1].max(brick[4]) + 1)
+        .max()
+        .unwrap_or(0);
+    let mut floor = vec![vec![0; depth]; width];
     let mut collapsed = Vec::new();
     for mut brick in bricks {
         let resting_coords = resting_on(&brick);
@@ -76,4 +87,13 @@ mod tests {
         let answer = crate::part1::solve(example_input);
         assert_eq!(answer, 5);
     }
+
+    #[test]
+    fn part1_wide_floor() {
+        let example_input = "0,0,1~12,0,1
+11,0,2~11,14,2
+0,0,4~0,0,4";
+        let answer = crate::part1::solve(example_input);
+        assert_eq!(answer, 2);
+    }
 }
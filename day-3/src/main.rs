@@ -1,143 +1,80 @@
 // Advent of Code - Day 3: Gear Ratios
 
-use std::collections::HashSet;
+pub mod schematic;
+
+use schematic::{Adjacency, EngineSchematic, Rule};
 
 const INPUT: &str = include_str!("./input.txt");
 
-#[derive(Default)]
-struct EngineSchematic {
-    parts: Vec<Part>,
-    symbols: HashSet<(i32, i32)>,
-    gears: HashSet<(i32, i32)>,
+fn solve_part_one(input: &str) -> u32 {
+    EngineSchematic::from(input)
+        .part_numbers(Adjacency::Diagonal)
+        .map(|number| number.value)
+        .sum()
 }
 
-struct Part {
-    number: u32,
-    border: HashSet<(i32, i32)>,
+fn solve_part_two(input: &str) -> u64 {
+    EngineSchematic::from(input)
+        .apply(&Rule::gear(), Adjacency::Diagonal)
+        .expect("Gear ratios should fit in 64 bits.")
 }
 
-impl Part {
-    fn new(row: usize, col: usize, ch: char) -> Self {
-        let row = row as i32;
-        let col = col as i32;
-        let border = HashSet::from([
-            (row - 1, col - 1),
-            (row, col - 1),
-            (row + 1, col - 1),
-            (row - 1, col),
-            (row + 1, col),
-            (row - 1, col + 1),
-            (row, col + 1),
-            (row + 1, col + 1),
-        ]);
-        Self {
-            number: ch
-                .to_digit(10)
-                .expect("Char should be convertable to a digit."),
-            border,
+fn main() {
+    // `day-3 --rule <rule>` sums the values of the symbols matching a rule like `*:2-2:product`,
+    // `day-3 --numbers-at <row,col>` and `day-3 --symbols-of <number>` query the adjacency,
+    // all of them only consider cells sharing an edge when `--orthogonal` is given
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let adjacency = if args.iter().any(|arg| arg == "--orthogonal") {
+        args.retain(|arg| arg != "--orthogonal");
+        Adjacency::Orthogonal
+    } else {
+        Adjacency::Diagonal
+    };
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer = solve_part_one(INPUT);
+            println!("Part one: {part_one_answer:?}");
+            let part_two_answer = solve_part_two(INPUT);
+            println!("Part two: {part_two_answer:?}");
         }
-    }
-
-    fn add_digit(&mut self, row: usize, col: usize, ch: char) {
-        let row = row as i32;
-        let col = col as i32;
-        self.border
-            .extend([(row - 1, col + 1), (row, col + 1), (row + 1, col + 1)]); // add new right hand border
-        self.number = self.number * 10
-            + ch.to_digit(10)
-                .expect("Char should be convertable to a digit."); // updates part number
-    }
-}
-
-fn solve_part_one(input: &str) -> u32 {
-    let mut engine_schematic = EngineSchematic::default();
-    let mut current_part: Option<Part> = None;
-    for (row, line) in input.lines().enumerate() {
-        for (col, ch) in line.char_indices() {
-            if ch.is_ascii_digit() {
-                if let Some(ref mut part) = current_part {
-                    part.add_digit(row, col, ch)
-                } else {
-                    current_part = Some(Part::new(row, col, ch))
-                }
-            } else {
-                if let Some(part) = current_part.take() {
-                    engine_schematic.parts.push(part)
-                }
-                if ch != '.' {
-                    engine_schematic.symbols.insert((row as i32, col as i32));
+        ["--rule", rule] => {
+            match EngineSchematic::from(INPUT).apply(&Rule::from(rule), adjacency) {
+                Some(answer) => println!("Sum of all matching symbols: {answer:?}"),
+                None => {
+                    eprintln!("The values of the matching symbols overflow 64 bits");
+                    std::process::exit(1);
                 }
             }
         }
-    }
-    engine_schematic
-        .parts
-        .iter()
-        .filter_map(|part| {
-            if part
-                .border
-                .intersection(&engine_schematic.symbols)
-                .next()
-                .is_some()
-            {
-                Some(part.number)
-            } else {
-                None
-            }
-        })
-        .sum()
-}
-
-fn solve_part_two(input: &str) -> u32 {
-    let mut engine_schematic = EngineSchematic::default();
-    let mut current_part: Option<Part> = None;
-    for (row, line) in input.lines().enumerate() {
-        for (col, ch) in line.char_indices() {
-            if ch.is_ascii_digit() {
-                if let Some(ref mut part) = current_part {
-                    part.add_digit(row, col, ch)
-                } else {
-                    current_part = Some(Part::new(row, col, ch))
-                }
-            } else {
-                if let Some(part) = current_part.take() {
-                    engine_schematic.parts.push(part)
-                }
-                if ch == '*' {
-                    engine_schematic.gears.insert((row as i32, col as i32));
-                }
-            }
+        ["--numbers-at", position] => {
+            let (row, col) = position
+                .split_once(',')
+                .expect("Position should be formatted as `row,col`.");
+            let schematic = EngineSchematic::from(INPUT);
+            let numbers = schematic.numbers_adjacent_to(
+                row.parse().expect("Row should be a number."),
+                col.parse().expect("Column should be a number."),
+                adjacency,
+            );
+            numbers
+                .iter()
+                .for_each(|number| println!("{} at {},{}", number.value, number.row, number.start));
         }
-    }
-    engine_schematic
-        .gears
-        .iter()
-        .flat_map(|gear| {
-            let matches: Vec<u32> = engine_schematic
-                .parts
+        ["--symbols-of", value] => {
+            let schematic = EngineSchematic::from(INPUT);
+            let symbols = schematic
+                .symbols_adjacent_to(value.parse().expect("Number should be numeric."), adjacency);
+            symbols
                 .iter()
-                .filter_map(|part| {
-                    if part.border.contains(gear) {
-                        Some(part.number)
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-            if matches.len() == 2 {
-                Some(matches[0] * matches[1])
-            } else {
-                None
-            }
-        })
-        .sum()
-}
-
-fn main() {
-    let part_one_answer = solve_part_one(INPUT);
-    println!("Part one: {part_one_answer:?}");
-    let part_two_answer: u32 = solve_part_two(INPUT);
-    println!("Part two: {part_two_answer:?}");
+                .for_each(|symbol| println!("{} at {},{}", symbol.ch, symbol.row, symbol.col));
+        }
+        _ => {
+            eprintln!(
+                "Usage: day-3 [--rule <rule> | --numbers-at <row,col> | --symbols-of <number>] [--orthogonal]"
+            );
+            std::process::exit(2);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -155,4 +92,15 @@ mod tests {
         let answer = crate::solve_part_two(example_input);
         assert_eq!(answer, 467835);
     }
+
+    #[test]
+    fn part2_as_rule() {
+        let example_input = include_str!("./example.txt");
+        let schematic = crate::EngineSchematic::from(example_input);
+        let answer = schematic.apply(
+            &crate::Rule::from("*:2-2:product"),
+            crate::Adjacency::Diagonal,
+        );
+        assert_eq!(answer, Some(467835));
+    }
 }
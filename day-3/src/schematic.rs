@@ -0,0 +1,361 @@
+// Advent of Code - Day 3: Gear Ratios Engine schematic
+
+use std::ops::RangeInclusive;
+
+/// Number in the schematic, spanning columns `start..end` of `row`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Number {
+    pub(crate) value: u32,
+    pub(crate) row: usize,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+/// Any character in the schematic that is neither a digit nor a `.`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Symbol {
+    pub(crate) ch: char,
+    pub(crate) row: usize,
+    pub(crate) col: usize,
+}
+
+/// Which neighbouring cells count as adjacent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Adjacency {
+    /// Only cells sharing an edge
+    Orthogonal,
+    /// Cells sharing an edge or a corner, as in the puzzle
+    Diagonal,
+}
+
+/// How the numbers adjacent to a symbol are combined into a single value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Combine {
+    Product,
+    Sum,
+    /// Concatenate the decimal digits in reading order, e.g. 12 and 3 give 123
+    Concatenation,
+}
+
+impl Combine {
+    /// Combined value of `numbers`, `None` if it does not fit in a `u64`
+    fn apply<'a>(&self, numbers: impl Iterator<Item = &'a Number>) -> Option<u64> {
+        let mut values = numbers.map(|number| number.value as u64);
+        match self {
+            Combine::Product => values.try_fold(1, u64::checked_mul),
+            Combine::Sum => values.try_fold(0, u64::checked_add),
+            Combine::Concatenation => values.try_fold(0, |acc: u64, value| {
+                acc.checked_mul(10_u64.pow(value.checked_ilog10().unwrap_or(0) + 1))?
+                    .checked_add(value)
+            }),
+        }
+    }
+}
+
+/// Rule that turns each matching symbol into a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Rule {
+    /// Symbol the rule applies to, `None` for every symbol
+    pub(crate) symbol: Option<char>,
+    /// Number of adjacent numbers a symbol needs for the rule to apply
+    pub(crate) neighbours: RangeInclusive<usize>,
+    pub(crate) combine: Combine,
+}
+
+impl Rule {
+    /// A gear: a `*` with exactly two adjacent numbers, valued at their product
+    pub(crate) fn gear() -> Self {
+        Rule {
+            symbol: Some('*'),
+            neighbours: 2..=2,
+            combine: Combine::Product,
+        }
+    }
+}
+
+impl From<&str> for Rule {
+    /// Parse a rule formatted as `<symbol|any>:<min>-<max>:<product|sum|concat>`, e.g. `*:2-2:product`
+    fn from(rule: &str) -> Self {
+        let mut parts = rule.split(':');
+        let symbol = match parts.next().expect("Rule should start with a symbol.") {
+            "any" => None,
+            symbol => Some(symbol.chars().next().expect("Symbol should not be empty.")),
+        };
+        let (min, max) = parts
+            .next()
+            .and_then(|range| range.split_once('-'))
+            .expect("Rule should contain a neighbour range like `2-2`.");
+        let combine = match parts.next() {
+            Some("product") => Combine::Product,
+            Some("sum") => Combine::Sum,
+            Some("concat") => Combine::Concatenation,
+            _ => panic!("Rule should end with `product`, `sum` or `concat`."),
+        };
+        Rule {
+            symbol,
+            neighbours: min.parse().expect("Minimum should be a number.")
+                ..=max.parse().expect("Maximum should be a number."),
+            combine,
+        }
+    }
+}
+
+impl Number {
+    /// Whether `symbol` touches this number with the given `adjacency`
+    fn touches(&self, symbol: &Symbol, adjacency: Adjacency) -> bool {
+        let rows = self.row.saturating_sub(1)..=self.row + 1;
+        let cols = self.start.saturating_sub(1)..=self.end;
+        let diagonal = rows.contains(&symbol.row) && cols.contains(&symbol.col);
+        match adjacency {
+            Adjacency::Diagonal => diagonal,
+            Adjacency::Orthogonal => {
+                diagonal && (symbol.row == self.row || (self.start..self.end).contains(&symbol.col))
+            }
+        }
+    }
+}
+
+/// Numbers and symbols of a schematic with an adjacency index between them
+#[derive(Debug, Default)]
+pub(crate) struct EngineSchematic {
+    pub(crate) numbers: Vec<Number>,
+    pub(crate) symbols: Vec<Symbol>,
+    /// Indices into `numbers` of the numbers adjacent to each symbol, in reading order
+    pub(crate) symbol_neighbours: Vec<Vec<usize>>,
+    /// Indices into `symbols` of the symbols adjacent to each number, in reading order
+    pub(crate) number_neighbours: Vec<Vec<usize>>,
+}
+
+impl EngineSchematic {
+    /// Numbers adjacent to the symbol at `(row, col)`, empty if there is no symbol
+    pub(crate) fn numbers_adjacent_to(
+        &self,
+        row: usize,
+        col: usize,
+        adjacency: Adjacency,
+    ) -> Vec<&Number> {
+        self.symbols
+            .binary_search_by_key(&(row, col), |symbol| (symbol.row, symbol.col))
+            .map(|symbol_idx| self.neighbours_of_symbol(symbol_idx, adjacency).collect())
+            .unwrap_or_default()
+    }
+
+    /// Symbols adjacent to any occurrence of the number `value`
+    pub(crate) fn symbols_adjacent_to(&self, value: u32, adjacency: Adjacency) -> Vec<&Symbol> {
+        self.numbers
+            .iter()
+            .zip(&self.number_neighbours)
+            .filter(|(number, _)| number.value == value)
+            .flat_map(|(number, symbols)| {
+                symbols
+                    .iter()
+                    .map(|&idx| &self.symbols[idx])
+                    .filter(move |symbol| number.touches(symbol, adjacency))
+            })
+            .collect()
+    }
+
+    /// Numbers adjacent to at least one symbol
+    pub(crate) fn part_numbers(&self, adjacency: Adjacency) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.number_neighbours)
+            .filter(move |(number, symbols)| {
+                symbols
+                    .iter()
+                    .any(|&idx| number.touches(&self.symbols[idx], adjacency))
+            })
+            .map(|(number, _)| number)
+    }
+
+    /// Sum of the values of all symbols that satisfy `rule`, `None` if a value
+    /// or the sum does not fit in a `u64`
+    pub(crate) fn apply(&self, rule: &Rule, adjacency: Adjacency) -> Option<u64> {
+        (0..self.symbols.len())
+            .filter(|&idx| rule.symbol.is_none_or(|ch| self.symbols[idx].ch == ch))
+            .map(|idx| {
+                self.neighbours_of_symbol(idx, adjacency)
+                    .collect::<Vec<&Number>>()
+            })
+            .filter(|numbers| rule.neighbours.contains(&numbers.len()))
+            .map(|numbers| rule.combine.apply(numbers.into_iter()))
+            .try_fold(0, |sum: u64, value| sum.checked_add(value?))
+    }
+
+    fn neighbours_of_symbol(
+        &self,
+        symbol_idx: usize,
+        adjacency: Adjacency,
+    ) -> impl Iterator<Item = &Number> {
+        let symbol = &self.symbols[symbol_idx];
+        self.symbol_neighbours[symbol_idx]
+            .iter()
+            .map(|&idx| &self.numbers[idx])
+            .filter(move |number| number.touches(symbol, adjacency))
+    }
+
+    fn link(&mut self, number_idx: usize, symbol_idx: usize) {
+        self.symbol_neighbours[symbol_idx].push(number_idx);
+        self.number_neighbours[number_idx].push(symbol_idx);
+    }
+
+    /// Link the numbers in `numbers` to the symbols in `symbols` that touch them
+    ///
+    /// Both ranges index a single row and are sorted by column, so the symbols
+    /// next to a number are found with a binary search.
+    fn link_rows(&mut self, numbers: std::ops::Range<usize>, symbols: std::ops::Range<usize>) {
+        for number_idx in numbers {
+            let (start, end) = {
+                let number = &self.numbers[number_idx];
+                (number.start.saturating_sub(1), number.end)
+            };
+            let row_symbols = &self.symbols[symbols.clone()];
+            let first = row_symbols.partition_point(|symbol| symbol.col < start);
+            let last = row_symbols.partition_point(|symbol| symbol.col <= end);
+            for symbol_idx in symbols.start + first..symbols.start + last {
+                self.link(number_idx, symbol_idx);
+            }
+        }
+    }
+}
+
+impl From<&str> for EngineSchematic {
+    /// Sweep over the rows once, linking each row to itself and the row above
+    ///
+    /// Together with the next row linking back to this one, every pair of
+    /// rows within a three-row window is linked exactly once.
+    fn from(input: &str) -> Self {
+        let mut schematic = EngineSchematic::default();
+        let mut previous_row = (0..0, 0..0);
+        for (row, line) in input.lines().enumerate() {
+            let (numbers_start, symbols_start) = (schematic.numbers.len(), schematic.symbols.len());
+            let mut current_number: Option<Number> = None;
+            for (col, ch) in line.char_indices() {
+                if let Some(digit) = ch.to_digit(10) {
+                    let number = current_number.get_or_insert(Number {
+                        value: 0,
+                        row,
+                        start: col,
+                        end: col,
+                    });
+                    number.value = number.value * 10 + digit;
+                    number.end = col + 1;
+                } else {
+                    schematic.numbers.extend(current_number.take());
+                    if ch != '.' {
+                        schematic.symbols.push(Symbol { ch, row, col });
+                    }
+                }
+            }
+            schematic.numbers.extend(current_number.take());
+            schematic
+                .symbol_neighbours
+                .resize(schematic.symbols.len(), Vec::new());
+            schematic
+                .number_neighbours
+                .resize(schematic.numbers.len(), Vec::new());
+
+            let current_row = (
+                numbers_start..schematic.numbers.len(),
+                symbols_start..schematic.symbols.len(),
+            );
+            schematic.link_rows(current_row.0.clone(), previous_row.1.clone());
+            schematic.link_rows(current_row.0.clone(), current_row.1.clone());
+            schematic.link_rows(previous_row.0.clone(), current_row.1.clone());
+            previous_row = current_row;
+        }
+        schematic
+            .symbol_neighbours
+            .iter_mut()
+            .for_each(|numbers| numbers.sort_unstable());
+        schematic
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schematic::{Adjacency, Combine, EngineSchematic, Number, Rule};
+
+    #[test]
+    fn adjacency_index() {
+        let schematic = EngineSchematic::from("12.7.\n..*..\n3...#\n..45.");
+        let values = |indices: &Vec<usize>| -> Vec<u32> {
+            indices
+                .iter()
+                .map(|&idx| schematic.numbers[idx].value)
+                .collect()
+        };
+        assert_eq!(values(&schematic.symbol_neighbours[0]), vec![12, 7]);
+        assert_eq!(values(&schematic.symbol_neighbours[1]), vec![45]);
+        assert!(schematic.number_neighbours[2].is_empty());
+    }
+
+    #[test]
+    fn orthogonal_adjacency() {
+        let schematic = EngineSchematic::from("12.7.\n..*..\n..5#.");
+        let values = |numbers: Vec<&Number>| -> Vec<u32> {
+            numbers.iter().map(|number| number.value).collect()
+        };
+        assert_eq!(
+            values(schematic.numbers_adjacent_to(1, 2, Adjacency::Diagonal)),
+            vec![12, 7, 5]
+        );
+        assert_eq!(
+            values(schematic.numbers_adjacent_to(1, 2, Adjacency::Orthogonal)),
+            vec![5]
+        );
+        assert_eq!(
+            schematic
+                .symbols_adjacent_to(5, Adjacency::Orthogonal)
+                .len(),
+            2
+        );
+        assert!(schematic
+            .symbols_adjacent_to(7, Adjacency::Orthogonal)
+            .is_empty());
+    }
+
+    #[test]
+    fn custom_rules() {
+        let schematic = EngineSchematic::from("12.7.\n..*..\n..5#.");
+        let rule = |symbol, combine| Rule {
+            symbol,
+            neighbours: 1..=3,
+            combine,
+        };
+        assert_eq!(
+            schematic.apply(
+                &rule(Some('*'), Combine::Concatenation),
+                Adjacency::Diagonal
+            ),
+            Some(1275)
+        );
+        assert_eq!(
+            schematic.apply(&rule(None, Combine::Sum), Adjacency::Diagonal),
+            Some(12 + 7 + 5 + 5)
+        );
+        assert_eq!(schematic.apply(&Rule::gear(), Adjacency::Diagonal), Some(0));
+    }
+
+    #[test]
+    fn overflowing_rules() {
+        let schematic = EngineSchematic::from("4000000000*4000000000\n4000000000..........");
+        let rule = |combine| Rule {
+            symbol: None,
+            neighbours: 1..=8,
+            combine,
+        };
+        assert_eq!(
+            schematic.apply(&rule(Combine::Sum), Adjacency::Diagonal),
+            Some(12_000_000_000)
+        );
+        assert_eq!(
+            schematic.apply(&rule(Combine::Product), Adjacency::Diagonal),
+            None
+        );
+        assert_eq!(
+            schematic.apply(&rule(Combine::Concatenation), Adjacency::Diagonal),
+            None
+        );
+    }
+}
@@ -0,0 +1,169 @@
+// Advent of Code - Day 4: Scratchcards Cascade
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use crate::Scratchcard;
+
+/// Rules for how winning cards hand out copies of the cards below them
+pub(crate) struct CopyRules {
+    /// Number of following cards that a card wins a copy of
+    pub(crate) window: fn(&Scratchcard) -> usize,
+    /// Maximum number of instances of a single card, `None` for no limit
+    pub(crate) max_copies: Option<u32>,
+}
+
+impl Default for CopyRules {
+    /// The puzzle's rules: one copy of each of the next `count_winning_numbers` cards
+    fn default() -> Self {
+        CopyRules {
+            window: |card| card.count_winning_numbers() as usize,
+            max_copies: None,
+        }
+    }
+}
+
+/// Outcome of processing all scratchcards, including where every copy came from
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Cascade {
+    /// Number of instances of each card, the original plus all won copies
+    pub(crate) copies: Vec<u32>,
+    /// Per card the `(earlier card, copies)` pairs that contributed to it
+    pub(crate) contributions: Vec<Vec<(usize, u32)>>,
+}
+
+impl CopyRules {
+    /// Number of instances after adding `copies` to `instances`, respecting the cap
+    fn add(&self, instances: u32, copies: u32) -> u32 {
+        let total = instances + copies;
+        self.max_copies.map_or(total, |max| total.min(max))
+    }
+}
+
+/// Copies won for the cards that are still to come while processing cards in order
+///
+/// Only the window of upcoming cards is kept, so memory is bounded by the
+/// largest window of a single card.
+#[derive(Debug, Default)]
+pub(crate) struct PendingCopies {
+    /// Index of the next card
+    next: usize,
+    /// Per upcoming card its instances and the `(earlier card, copies)` contributions
+    upcoming: VecDeque<(u32, Vec<(usize, u32)>)>,
+}
+
+impl PendingCopies {
+    /// Process the next card, returning its instances and where its copies came from
+    pub(crate) fn process(
+        &mut self,
+        card: &Scratchcard,
+        rules: &CopyRules,
+    ) -> (u32, Vec<(usize, u32)>) {
+        let idx = self.next;
+        self.next += 1;
+        let original = || (rules.add(0, 1), Vec::new());
+        let (copies, contributions) = self.upcoming.pop_front().unwrap_or_else(original);
+        let window = (rules.window)(card);
+        if self.upcoming.len() < window {
+            self.upcoming.resize_with(window, original);
+        }
+        for (instances, won_contributions) in self.upcoming.iter_mut().take(window) {
+            let total = rules.add(*instances, copies);
+            let added = total - *instances;
+            if added > 0 {
+                *instances = total;
+                won_contributions.push((idx, added));
+            }
+        }
+        (copies, contributions)
+    }
+}
+
+impl Cascade {
+    pub(crate) fn new(cards: &[Scratchcard], rules: &CopyRules) -> Self {
+        let mut pending = PendingCopies::default();
+        let (copies, contributions) = cards
+            .iter()
+            .map(|card| pending.process(card, rules))
+            .unzip();
+        Cascade {
+            copies,
+            contributions,
+        }
+    }
+
+    /// Total number of scratchcards, originals and copies
+    pub(crate) fn total(&self) -> u32 {
+        self.copies.iter().sum()
+    }
+
+    /// Export the cascade as a Graphviz DOT graph, with edges labelled by the number of copies
+    pub(crate) fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph cascade {\n");
+        for (idx, copies) in self.copies.iter().enumerate() {
+            writeln!(
+                dot,
+                "    card{0} [label=\"Card {0}\\n{1}x\"];",
+                idx + 1,
+                copies
+            )
+            .unwrap();
+        }
+        for (idx, contributions) in self.contributions.iter().enumerate() {
+            for (source, copies) in contributions {
+                writeln!(
+                    dot,
+                    "    card{} -> card{} [label=\"{}\"];",
+                    source + 1,
+                    idx + 1,
+                    copies
+                )
+                .unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cascade::{Cascade, CopyRules};
+    use crate::Scratchcard;
+
+    fn example_cards() -> Vec<Scratchcard> {
+        include_str!("./example.txt")
+            .lines()
+            .map(Scratchcard::from)
+            .collect()
+    }
+
+    #[test]
+    fn provenance() {
+        let cascade = Cascade::new(&example_cards(), &CopyRules::default());
+        assert_eq!(cascade.copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.contributions[3], vec![(0, 1), (1, 2), (2, 4)]);
+        assert_eq!(cascade.total(), 30);
+    }
+
+    #[test]
+    fn capped_copies() {
+        let rules = CopyRules {
+            max_copies: Some(3),
+            ..Default::default()
+        };
+        let cascade = Cascade::new(&example_cards(), &rules);
+        assert_eq!(cascade.copies, vec![1, 2, 3, 3, 3, 1]);
+        assert_eq!(cascade.contributions[4], vec![(0, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn single_card_window() {
+        let rules = CopyRules {
+            window: |card| card.count_winning_numbers().min(1) as usize,
+            ..Default::default()
+        };
+        let cascade = Cascade::new(&example_cards(), &rules);
+        assert_eq!(cascade.copies, vec![1, 2, 3, 4, 5, 1]);
+    }
+}
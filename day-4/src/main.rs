@@ -1,6 +1,11 @@
 // Advent of Code - Day 4: Scratchcards
 
+pub mod cascade;
+
 use std::collections::HashSet;
+use std::io::BufRead;
+
+use cascade::{Cascade, CopyRules, PendingCopies};
 
 const INPUT: &str = include_str!("./input.txt");
 
@@ -21,63 +26,92 @@ impl Scratchcard {
     }
 }
 
+impl From<&str> for Scratchcard {
+    fn from(line: &str) -> Self {
+        let (winning_numbers_seq, card_numbers_seq) =
+            line.split_once(':').unwrap().1.split_once('|').unwrap();
+        let winning_numbers: HashSet<u32> = winning_numbers_seq
+            .split_whitespace()
+            .map(|num| num.parse::<u32>().unwrap())
+            .collect();
+        let card_numbers: HashSet<u32> = card_numbers_seq
+            .split_whitespace()
+            .map(|num| num.parse::<u32>().unwrap())
+            .collect();
+        Scratchcard {
+            winning_numbers,
+            card_numbers,
+        }
+    }
+}
+
 fn solve_part_one(input: &str) -> u32 {
-    input
+    solve_part_one_streaming(input.as_bytes())
+}
+
+/// Solve part one card by card from any buffered reader, e.g. stdin
+fn solve_part_one_streaming<R: BufRead>(reader: R) -> u32 {
+    reader
         .lines()
-        .map(|line| {
-            let (winning_numbers_seq, card_numbers_seq) =
-                line.split_once(':').unwrap().1.split_once('|').unwrap();
-            let winning_numbers: HashSet<u32> = winning_numbers_seq
-                .split_whitespace()
-                .map(|num| num.parse::<u32>().unwrap())
-                .collect();
-            let card_numbers: HashSet<u32> = card_numbers_seq
-                .split_whitespace()
-                .map(|num| num.parse::<u32>().unwrap())
-                .collect();
-            Scratchcard {
-                winning_numbers,
-                card_numbers,
-            }
-        })
+        .map(|line| Scratchcard::from(line.expect("Input should be readable.").as_str()))
         .map(|scratchcard| scratchcard.value())
         .sum()
 }
 
 fn solve_part_two(input: &str) -> u32 {
-    let original_cards: Vec<Scratchcard> = input
+    solve_part_two_streaming(input.as_bytes())
+}
+
+/// Solve part two card by card from any buffered reader, e.g. stdin
+///
+/// Only the copies won for upcoming cards are kept, so memory is bounded by
+/// the largest number of winning numbers on a single card.
+fn solve_part_two_streaming<R: BufRead>(reader: R) -> u32 {
+    let rules = CopyRules::default();
+    let mut pending_copies = PendingCopies::default();
+    reader
         .lines()
-        .map(|line| {
-            let (winning_numbers_seq, card_numbers_seq) =
-                line.split_once(':').unwrap().1.split_once('|').unwrap();
-            let winning_numbers: HashSet<u32> = winning_numbers_seq
-                .split_whitespace()
-                .map(|num| num.parse::<u32>().unwrap())
-                .collect();
-            let card_numbers: HashSet<u32> = card_numbers_seq
-                .split_whitespace()
-                .map(|num| num.parse::<u32>().unwrap())
-                .collect();
-            Scratchcard {
-                winning_numbers,
-                card_numbers,
-            }
-        })
-        .collect();
-    let mut card_counter: Vec<u32> = vec![1u32; original_cards.len()];
-    original_cards.iter().enumerate().for_each(|(idx, card)| {
-        (idx + 1..idx + 1 + card.count_winning_numbers() as usize).for_each(|i| {
-            card_counter[i] += card_counter[idx];
-        });
-    });
-    card_counter.iter().sum()
+        .map(|line| Scratchcard::from(line.expect("Input should be readable.").as_str()))
+        .map(|card| pending_copies.process(&card, &rules).0)
+        .sum()
 }
 
 fn main() {
-    let part_one_answer = solve_part_one(INPUT);
-    println!("Part one: {part_one_answer:?}");
-    let part_two_answer: u32 = solve_part_two(INPUT);
-    println!("Part two: {part_two_answer:?}");
+    // `day-4 - <part>` solves a single part streaming from stdin and
+    // `day-4 --cascade [max copies]` prints the part two cascade as Graphviz DOT
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer = solve_part_one(INPUT);
+            println!("Part one: {part_one_answer:?}");
+            let part_two_answer: u32 = solve_part_two(INPUT);
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["-", "1"] => {
+            let part_one_answer = solve_part_one_streaming(std::io::stdin().lock());
+            println!("Part one: {part_one_answer:?}");
+        }
+        ["-", "2"] => {
+            let part_two_answer = solve_part_two_streaming(std::io::stdin().lock());
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["--cascade", ref max_copies @ ..] if max_copies.len() <= 1 => {
+            let cards: Vec<Scratchcard> = INPUT.lines().map(Scratchcard::from).collect();
+            let rules = CopyRules {
+                max_copies: max_copies
+                    .first()
+                    .map(|max| max.parse().expect("Maximum copies should be a number.")),
+                ..Default::default()
+            };
+            let cascade = Cascade::new(&cards, &rules);
+            print!("{}", cascade.to_dot());
+            eprintln!("Total scratchcards: {}", cascade.total());
+        }
+        _ => {
+            eprintln!("Usage: day-4 [- <1|2> | --cascade [max copies]]");
+            std::process::exit(2);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -23,6 +23,97 @@ impl Mapping {
         }
         val
     }
+
+    /// Split a range of values at the boundaries of the maps, returning each
+    /// part together with the offset that applies to it
+    fn split(&self, range: std::ops::Range<u64>) -> Vec<(std::ops::Range<u64>, i64)> {
+        let mut parts = Vec::new();
+        let mut cur = range.start;
+        for map in &self.maps {
+            if cur >= range.end || map.range.start >= range.end {
+                break;
+            }
+            if map.range.end <= cur {
+                continue;
+            }
+            if cur < map.range.start {
+                parts.push((cur..map.range.start, 0));
+                cur = map.range.start;
+            }
+            let end = range.end.min(map.range.end);
+            parts.push((cur..end, map.offset));
+            cur = end;
+        }
+        if cur < range.end {
+            parts.push((cur..range.end, 0));
+        }
+        parts
+    }
+
+    /// Map a whole range of values, splitting it at the boundaries of the maps
+    fn apply_range(&self, range: std::ops::Range<u64>) -> Vec<std::ops::Range<u64>> {
+        self.split(range)
+            .into_iter()
+            .map(|(range, offset)| shift(&range, offset))
+            .collect()
+    }
+
+    /// Single mapping that is equivalent to applying `self` and then `next`
+    fn compose(&self, next: &Mapping) -> Mapping {
+        let mut composed = Mapping::new();
+        for (range, offset) in self.split(0..u64::MAX) {
+            for (image, next_offset) in next.split(shift(&range, offset)) {
+                if offset + next_offset != 0 {
+                    composed.add_map(Map {
+                        range: shift(&image, -offset),
+                        offset: offset + next_offset,
+                    });
+                }
+            }
+        }
+        composed
+    }
+
+    /// All values that are mapped onto `val`, in ascending order
+    fn preimage(&self, val: u64) -> Vec<u64> {
+        let mut values: Vec<u64> = self
+            .maps
+            .iter()
+            .filter_map(|map| {
+                u64::try_from(val as i64 - map.offset)
+                    .ok()
+                    .filter(|v| map.range.contains(v))
+            })
+            .collect();
+        if !self.maps.iter().any(|map| map.range.contains(&val)) {
+            values.push(val);
+        }
+        values.sort_unstable();
+        values
+    }
+}
+
+/// Move a range by `offset`
+fn shift(range: &std::ops::Range<u64>, offset: i64) -> std::ops::Range<u64> {
+    (range.start as i64 + offset) as u64..(range.end as i64 + offset) as u64
+}
+
+impl std::fmt::Display for Mapping {
+    /// Table of all ranges that are moved, all other values map onto themselves
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:>24} {:>24} {:>12}", "source", "destination", "offset")?;
+        for map in &self.maps {
+            let destination = shift(&map.range, map.offset);
+            writeln!(
+                f,
+                "{:>24} {:>24} {:>+12}",
+                format!("{}..{}", map.range.start, map.range.end),
+                format!("{}..{}", destination.start, destination.end),
+                map.offset
+            )?;
+        }
+        write!(f, "{:>24} {:>24} {:>+12}", "otherwise", "unchanged", 0)
+    }
 }
 
 #[derive(Debug)]
@@ -40,43 +131,66 @@ impl Map {
     }
 }
 
-fn solve_part_one(input: &str) -> u64 {
-    let mut lines = input.lines().filter(|line| !line.is_empty());
-    let seeds: Vec<u64> = lines
-        .next()
-        .unwrap()
-        .split_once(": ")
-        .unwrap()
-        .1
-        .split_whitespace()
-        .map(|seed| seed.parse().unwrap())
-        .collect();
-
-    let mut mappings: Vec<Mapping> = Vec::new();
-    let mut mapping = Mapping::new();
-    for line in lines {
-        if line.contains("map") {
-            if !mapping.maps.is_empty() {
-                mappings.push(mapping);
-                mapping = Mapping::new();
-            }
-            continue;
-        }
+/// Seeds and the mappings from seed to location, in the order they have to be applied
+struct Almanac {
+    seeds: Vec<u64>,
+    mappings: Vec<Mapping>,
+}
 
-        let nums: Vec<u64> = line
+impl From<&str> for Almanac {
+    fn from(input: &str) -> Self {
+        let mut lines = input.lines().filter(|line| !line.is_empty());
+        let seeds: Vec<u64> = lines
+            .next()
+            .unwrap()
+            .split_once(": ")
+            .unwrap()
+            .1
             .split_whitespace()
-            .map(|v| v.parse().unwrap())
+            .map(|seed| seed.parse().unwrap())
             .collect();
-        mapping.add_map(Map::from_almanac(nums[0], nums[1], nums[2]));
+
+        let mut mappings: Vec<Mapping> = Vec::new();
+        let mut mapping = Mapping::new();
+        for line in lines {
+            if line.contains("map") {
+                if !mapping.maps.is_empty() {
+                    mappings.push(mapping);
+                    mapping = Mapping::new();
+                }
+                continue;
+            }
+
+            let nums: Vec<u64> = line
+                .split_whitespace()
+                .map(|v| v.parse().unwrap())
+                .collect();
+            mapping.add_map(Map::from_almanac(nums[0], nums[1], nums[2]));
+        }
+        if !mapping.maps.is_empty() {
+            mappings.push(mapping);
+        }
+        Almanac { seeds, mappings }
     }
-    if !mapping.maps.is_empty() {
-        mappings.push(mapping);
+}
+
+impl Almanac {
+    /// All mappings composed into a single seed-to-location mapping
+    fn seed_to_location(&self) -> Mapping {
+        self.mappings
+            .iter()
+            .fold(Mapping::new(), |composed, mapping| {
+                composed.compose(mapping)
+            })
     }
+}
 
+fn solve_part_one(input: &str) -> u64 {
+    let almanac = Almanac::from(input);
     let mut lowest_location: u64 = u64::MAX;
-    for seed in seeds {
+    for &seed in &almanac.seeds {
         let mut cur = seed;
-        for mapping in &mappings {
+        for mapping in &almanac.mappings {
             cur = mapping.apply(cur)
         }
         lowest_location = lowest_location.min(cur);
@@ -85,59 +199,50 @@ fn solve_part_one(input: &str) -> u64 {
 }
 
 fn solve_part_two(input: &str) -> u64 {
-    let mut lines = input.lines().filter(|line| !line.is_empty());
-    let seed_ranges = lines
-        .next()
-        .unwrap()
-        .split_once(": ")
-        .unwrap()
-        .1
-        .split_whitespace()
-        .map(|v| v.parse().unwrap())
-        .collect::<Vec<u64>>()
+    let almanac = Almanac::from(input);
+    let seed_ranges = almanac
+        .seeds
         .chunks_exact(2)
         .map(|range| range[0]..range[0] + range[1])
         .collect::<Vec<std::ops::Range<u64>>>();
 
-    let mut mappings: Vec<Mapping> = Vec::new();
-    let mut mapping = Mapping::new();
-    for line in lines {
-        if line.contains("map") {
-            if !mapping.maps.is_empty() {
-                mappings.push(mapping);
-                mapping = Mapping::new();
-            }
-            continue;
-        }
-
-        let nums: Vec<u64> = line
-            .split_whitespace()
-            .map(|v| v.parse().unwrap())
-            .collect();
-        mapping.add_map(Map::from_almanac(nums[0], nums[1], nums[2]));
-    }
-    if !mapping.maps.is_empty() {
-        mappings.push(mapping);
-    }
-
-    let mut lowest_location = u64::MAX;
-    for seed_range in seed_ranges {
-        for seed in seed_range {
-            let mut cur = seed;
-            for mapping in &mappings {
-                cur = mapping.apply(cur)
-            }
-            lowest_location = lowest_location.min(cur);
-        }
-    }
-    lowest_location
+    almanac
+        .mappings
+        .iter()
+        .fold(seed_ranges, |ranges, mapping| {
+            ranges
+                .into_iter()
+                .flat_map(|range| mapping.apply_range(range))
+                .collect()
+        })
+        .iter()
+        .map(|range| range.start)
+        .min()
+        .unwrap()
 }
 
 fn main() {
-    let part_one_answer = solve_part_one(INPUT);
-    println!("Part one: {part_one_answer:?}");
-    let part_two_answer = solve_part_two(INPUT);
-    println!("Part two: {part_two_answer:?}");
+    // `day-5 --table` prints the composed seed-to-location mapping and
+    // `day-5 --seeds-at <location>` lists the seeds that end up at a location
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer = solve_part_one(INPUT);
+            println!("Part one: {part_one_answer:?}");
+            let part_two_answer = solve_part_two(INPUT);
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["--table"] => println!("{}", Almanac::from(INPUT).seed_to_location()),
+        ["--seeds-at", location] => {
+            let location = location.parse().expect("Location should be a number.");
+            let seeds = Almanac::from(INPUT).seed_to_location().preimage(location);
+            println!("Seeds at location {location}: {seeds:?}");
+        }
+        _ => {
+            eprintln!("Usage: day-5 [--table | --seeds-at <location>]");
+            std::process::exit(2);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -155,4 +260,26 @@ mod tests {
         let answer = crate::solve_part_two(example_input);
         assert_eq!(answer, 46);
     }
+
+    #[test]
+    fn apply_range() {
+        let mut mapping = crate::Mapping::new();
+        mapping.add_map(crate::Map::from_almanac(50, 98, 2));
+        mapping.add_map(crate::Map::from_almanac(52, 50, 48));
+        assert_eq!(mapping.apply_range(40..100), vec![40..50, 52..100, 50..52]);
+        assert_eq!(mapping.apply_range(100..110), vec![100..110]);
+    }
+
+    #[test]
+    fn composed_mapping() {
+        let example_input = include_str!("./example.txt");
+        let seed_to_location = crate::Almanac::from(example_input).seed_to_location();
+        let locations: Vec<u64> = [79, 14, 55, 13]
+            .iter()
+            .map(|&seed| seed_to_location.apply(seed))
+            .collect();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+        assert_eq!(seed_to_location.preimage(82), vec![79]);
+        assert_eq!(seed_to_location.preimage(46), vec![82]);
+    }
 }
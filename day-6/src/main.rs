@@ -1,56 +1,180 @@
 // Advent of Code - Day 6: Wait For It
 
+pub mod race;
+
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+use num_bigint::BigUint;
+use race::{Phase, Profile, Race};
+
 const INPUT: &str = include_str!("./input.txt");
 
-fn calculate_distance(race_duration: &u64, charge_duration: u64) -> u64 {
+fn calculate_distance(race_duration: &BigUint, charge_duration: &BigUint) -> BigUint {
     let move_duration = race_duration - charge_duration;
     move_duration * charge_duration
 }
 
-fn solve_part_one(input: &str) -> u64 {
-    fn parse_line(line: &str) -> Vec<u64> {
+/// Charge durations that beat the `record`, or `None` if the record can't be beaten
+///
+/// The distance `c * (time - c)` exceeds the record strictly between the roots
+/// of `c^2 - time * c + record = 0`. The integer square root of the
+/// discriminant puts the lower root within one of its exact value, so the
+/// first winning charge duration is found by checking the few durations around
+/// it; ties with the record don't count as a win. The interval is symmetric
+/// around `time / 2`, which gives the last winning charge duration.
+fn winning_interval(time: &BigUint, record: &BigUint) -> Option<RangeInclusive<BigUint>> {
+    let discriminant = time * time;
+    let four_record = record * 4_u32;
+    if discriminant <= four_record {
+        return None;
+    }
+    let root = (discriminant - four_record).sqrt().min(time.clone());
+    let estimate = (time - root) / 2_u32;
+    let lowest = if estimate > BigUint::ZERO {
+        estimate - 1_u32
+    } else {
+        estimate
+    };
+    let start = (0..4_u32)
+        .map(|delta| &lowest + delta)
+        .find(|charge| charge <= time && &calculate_distance(time, charge) > record)?;
+    let end = time - &start;
+    Some(start..=end)
+}
+
+/// Number of charge durations that beat the `record`
+fn count_ways(time: &BigUint, record: &BigUint) -> BigUint {
+    winning_interval(time, record)
+        .map(|interval| interval.end() - interval.start() + 1_u32)
+        .unwrap_or_default()
+}
+
+/// Read the `Time:` and `Distance:` lines from a buffered reader
+fn read_lines<R: BufRead>(reader: R) -> (String, String) {
+    let mut lines = reader
+        .lines()
+        .map(|line| line.expect("Input should be readable."));
+    let times = lines.next().expect("Input should contain a time line.");
+    let records = lines.next().expect("Input should contain a distance line.");
+    (times, records)
+}
+
+fn solve_part_one(input: &str) -> BigUint {
+    solve_part_one_streaming(input.as_bytes())
+}
+
+/// Solve part one from any buffered reader, e.g. stdin
+fn solve_part_one_streaming<R: BufRead>(reader: R) -> BigUint {
+    let (times, records) = read_lines(reader);
+    parse_races(&times, &records)
+        .iter()
+        .map(|(time, record)| count_ways(time, record))
+        .product()
+}
+
+/// Parse the separate races of part one as `(time, record)` pairs
+fn parse_races(times: &str, records: &str) -> Vec<(BigUint, BigUint)> {
+    fn parse_line(line: &str) -> Vec<BigUint> {
         line.split_whitespace()
             .skip(1)
-            .flat_map(|v| v.parse::<u64>())
-            .collect::<Vec<u64>>()
+            .flat_map(|v| v.parse::<BigUint>())
+            .collect::<Vec<BigUint>>()
     }
-    let mut lines = input.lines();
-    let times = parse_line(lines.next().unwrap());
-    let records = parse_line(lines.next().unwrap());
-    times
-        .iter()
-        .zip(records.iter())
-        .map(|(&time, &record)| {
-            (1..time)
-                .map(|charge| calculate_distance(&time, charge))
-                .filter(|&distance| distance > record)
-                .count() as u64
-        })
-        .product::<u64>()
+    parse_line(times)
+        .into_iter()
+        .zip(parse_line(records))
+        .collect()
 }
 
-fn solve_part_two(input: &str) -> u64 {
-    fn parse_line(line: &str) -> u64 {
+fn solve_part_two(input: &str) -> BigUint {
+    solve_part_two_streaming(input.as_bytes())
+}
+
+/// Solve part two from any buffered reader, e.g. stdin
+fn solve_part_two_streaming<R: BufRead>(reader: R) -> BigUint {
+    fn parse_line(line: &str) -> BigUint {
         line.chars()
             .filter(|ch| ch.is_ascii_digit())
             .collect::<String>()
-            .parse::<u64>()
+            .parse::<BigUint>()
             .unwrap()
     }
-    let mut lines = input.lines();
-    let time = parse_line(lines.next().unwrap());
-    let record = parse_line(lines.next().unwrap());
-    (1..time)
-        .map(|charge| calculate_distance(&time, charge))
-        .filter(|&distance| distance > record)
-        .count() as u64
+    let (time, record) = read_lines(reader);
+    count_ways(&parse_line(&time), &parse_line(&record))
 }
 
 fn main() {
-    let part_one_answer = solve_part_one(INPUT);
-    println!("Part one: {part_one_answer:?}");
-    let part_two_answer = solve_part_two(INPUT);
-    println!("Part two: {part_two_answer:?}");
+    // `day-6 - <part>` solves a single part streaming from stdin and
+    // `day-6 --intervals` prints the winning charge durations of every race of part one and
+    // `day-6 --race <record> <duration:profile>[,...] [penalty]` solves a race variant
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer = solve_part_one(INPUT);
+            println!("Part one: {part_one_answer:?}");
+            let part_two_answer = solve_part_two(INPUT);
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["-", "1"] => {
+            let part_one_answer = solve_part_one_streaming(std::io::stdin().lock());
+            println!("Part one: {part_one_answer:?}");
+        }
+        ["-", "2"] => {
+            let part_two_answer = solve_part_two_streaming(std::io::stdin().lock());
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["--intervals"] => {
+            let (times, records) = read_lines(INPUT.as_bytes());
+            for (time, record) in parse_races(&times, &records) {
+                match winning_interval(&time, &record) {
+                    Some(interval) => println!(
+                        "Race of {time} ms: charge {}..={} ms to beat {record} mm ({} ways)",
+                        interval.start(),
+                        interval.end(),
+                        count_ways(&time, &record)
+                    ),
+                    None => println!("Race of {time} ms: {record} mm can't be beaten"),
+                }
+            }
+        }
+        ["--race", record, phases, ref penalty @ ..] if penalty.len() <= 1 => {
+            let race = Race {
+                phases: phases
+                    .split(',')
+                    .map(|phase| {
+                        let (duration, profile) = phase
+                            .split_once(':')
+                            .expect("Phase should be formatted as `duration:profile`.");
+                        Phase {
+                            duration: duration.parse().expect("Duration should be a number."),
+                            profile: Profile::from(profile),
+                        }
+                    })
+                    .collect(),
+                penalty: penalty
+                    .first()
+                    .map_or(0, |p| p.parse().expect("Penalty should be a number.")),
+                ..Race::new(0, record.parse().expect("Record should be a number."))
+            };
+            match race.winning_window() {
+                Some(window) => println!(
+                    "Charge {}..={} ms to beat {} mm ({} ways)",
+                    window.start(),
+                    window.end(),
+                    race.record,
+                    window.end() - window.start() + 1
+                ),
+                None => println!("{} mm can't be beaten", race.record),
+            }
+        }
+        _ => {
+            eprintln!(
+                "Usage: day-6 [- <1|2> | --intervals | --race <record> <duration:profile>[,...] [penalty]]"
+            );
+            std::process::exit(2);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -59,13 +183,33 @@ mod tests {
     fn part1() {
         let example_input = include_str!("./example.txt");
         let answer = crate::solve_part_one(example_input);
-        assert_eq!(answer, 288);
+        assert_eq!(answer, num_bigint::BigUint::from(288_u32));
     }
 
     #[test]
     fn part2() {
         let example_input = include_str!("./example.txt");
         let answer = crate::solve_part_two(example_input);
-        assert_eq!(answer, 71503);
+        assert_eq!(answer, num_bigint::BigUint::from(71503_u32));
+    }
+
+    #[test]
+    fn winning_interval_ties() {
+        let interval = |time: u32, record: u32| {
+            crate::winning_interval(&time.into(), &record.into())
+                .map(|interval| (interval.start().clone(), interval.end().clone()))
+        };
+        assert_eq!(interval(7, 9), Some((2_u32.into(), 5_u32.into())));
+        // charging 10 or 20 ms ties with the record, which doesn't count as a win
+        assert_eq!(interval(30, 200), Some((11_u32.into(), 19_u32.into())));
+        assert_eq!(interval(10, 25), None);
+        assert_eq!(interval(10, 24), Some((5_u32.into(), 5_u32.into())));
+    }
+
+    #[test]
+    fn beyond_u64() {
+        let time: num_bigint::BigUint = "100000000000000000000000000000".parse().unwrap();
+        let answer = crate::count_ways(&time, &num_bigint::BigUint::ZERO);
+        assert_eq!(answer, time - 1_u32);
     }
 }
@@ -0,0 +1,224 @@
+// Advent of Code - Day 6: Wait For It Race variants
+
+use std::ops::RangeInclusive;
+
+/// How the charge duration translates into the speed of the boat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Profile {
+    /// Speed equals the charge duration, as in the puzzle
+    Linear,
+    /// Speed equals the charge duration up to `max_speed`
+    Capped { max_speed: u64 },
+}
+
+impl Profile {
+    pub(crate) fn speed(&self, charge: u64) -> u64 {
+        match self {
+            Profile::Linear => charge,
+            Profile::Capped { max_speed } => charge.min(*max_speed),
+        }
+    }
+}
+
+impl From<&str> for Profile {
+    /// Parse `linear` or `capped:<max speed>`
+    fn from(profile: &str) -> Self {
+        match profile.split_once(':') {
+            None if profile == "linear" => Profile::Linear,
+            Some(("capped", max_speed)) => Profile::Capped {
+                max_speed: max_speed
+                    .parse()
+                    .expect("Maximum speed should be a number."),
+            },
+            _ => panic!("Profile should be `linear` or `capped:<max speed>`"),
+        }
+    }
+}
+
+/// Part of a race during which the boat moves according to `profile`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Phase {
+    pub(crate) duration: u64,
+    pub(crate) profile: Profile,
+}
+
+/// Race consisting of consecutive phases, the boat is charged once at the start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Race {
+    pub(crate) phases: Vec<Phase>,
+    pub(crate) record: u128,
+    /// Milliseconds the boat stays idle after charging, per millisecond charged
+    pub(crate) penalty: u64,
+}
+
+impl Race {
+    /// Single-phase race with the puzzle's rules
+    pub(crate) fn new(time: u64, record: u64) -> Self {
+        Race {
+            phases: vec![Phase {
+                duration: time,
+                profile: Profile::Linear,
+            }],
+            record: record as u128,
+            penalty: 0,
+        }
+    }
+
+    /// Total duration of all phases, saturating at `u64::MAX`
+    pub(crate) fn duration(&self) -> u64 {
+        self.phases.iter().fold(0, |duration: u64, phase| {
+            duration.saturating_add(phase.duration)
+        })
+    }
+
+    /// Distance travelled when charging the boat for `charge` milliseconds
+    pub(crate) fn distance(&self, charge: u64) -> u128 {
+        let departure = charge.saturating_add(charge.saturating_mul(self.penalty));
+        let mut phase_start: u64 = 0;
+        let mut distance: u128 = 0;
+        for phase in &self.phases {
+            let phase_end = phase_start.saturating_add(phase.duration);
+            let moving = phase_end.saturating_sub(departure.max(phase_start));
+            distance =
+                distance.saturating_add(phase.profile.speed(charge) as u128 * moving as u128);
+            phase_start = phase_end;
+        }
+        distance
+    }
+
+    /// Charge durations that beat the record, found by binary search
+    ///
+    /// This assumes the distance is unimodal in the charge duration, which is
+    /// the case for a single phase with any of the profiles.
+    pub(crate) fn winning_window(&self) -> Option<RangeInclusive<u64>> {
+        winning_window(self.duration(), self.record, |charge| self.distance(charge))
+    }
+}
+
+/// First value in `range` for which `predicate` is false, assuming it is true
+/// for a prefix of `range` and false for the rest
+///
+/// The result is one past the end of `range` if `predicate` holds for all of
+/// it, which is why it is a `u128`.
+fn partition_point(range: RangeInclusive<u64>, predicate: impl Fn(u64) -> bool) -> u128 {
+    let (mut low, mut high) = (*range.start() as u128, *range.end() as u128 + 1);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if predicate(mid as u64) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// Charge durations in `0..=max_charge` for which `distance` beats the `record`
+///
+/// `distance` has to be monotone or unimodal: non-decreasing up to its peak
+/// and non-increasing after it, without plateaus before the peak. The peak and
+/// both edges of the winning window are each found with a binary search.
+pub(crate) fn winning_window(
+    max_charge: u64,
+    record: u128,
+    distance: impl Fn(u64) -> u128,
+) -> Option<RangeInclusive<u64>> {
+    let peak = partition_point(0..=max_charge.saturating_sub(1), |charge| {
+        distance(charge) < distance(charge + 1)
+    })
+    .min(max_charge as u128) as u64;
+    if distance(peak) <= record {
+        return None;
+    }
+    // Both edges lie within `0..=max_charge`, as the record is beaten at the peak
+    let start = partition_point(0..=peak, |charge| distance(charge) <= record) as u64;
+    let end = (partition_point(peak..=max_charge, |charge| distance(charge) > record) - 1) as u64;
+    Some(start..=end)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::race::{winning_window, Phase, Profile, Race};
+
+    #[test]
+    fn puzzle_races() {
+        assert_eq!(Race::new(7, 9).winning_window(), Some(2..=5));
+        assert_eq!(Race::new(30, 200).winning_window(), Some(11..=19));
+        assert_eq!(Race::new(71530, 940200).winning_window(), Some(14..=71516));
+        assert_eq!(Race::new(10, 25).winning_window(), None);
+    }
+
+    #[test]
+    fn capped_speed() {
+        let race = Race {
+            phases: vec![Phase {
+                duration: 30,
+                profile: Profile::Capped { max_speed: 12 },
+            }],
+            ..Race::new(30, 200)
+        };
+        // c * (30 - c) > 200 from c = 11 up to the cap, 12 * (30 - c) > 200 for c <= 13
+        assert_eq!(race.winning_window(), Some(11..=13));
+    }
+
+    #[test]
+    fn charge_penalty_and_phases() {
+        let race = Race {
+            penalty: 1,
+            ..Race::new(30, 100)
+        };
+        // c * (30 - 2c) > 100 for 5 < c < 10
+        assert_eq!(race.winning_window(), Some(6..=9));
+
+        let race = Race {
+            phases: vec![
+                Phase {
+                    duration: 10,
+                    profile: Profile::Linear,
+                },
+                Phase {
+                    duration: 10,
+                    profile: Profile::Capped { max_speed: 5 },
+                },
+            ],
+            ..Race::new(0, 70)
+        };
+        assert_eq!(race.distance(5), 75);
+        assert_eq!(race.distance(6), 74);
+        assert_eq!(race.winning_window(), Some(5..=7));
+    }
+
+    #[test]
+    fn monotone_distance() {
+        assert_eq!(
+            winning_window(100, 49, |charge| charge as u128),
+            Some(50..=100)
+        );
+        assert_eq!(
+            winning_window(100, 49, |charge| 100 - charge as u128),
+            Some(0..=50)
+        );
+    }
+
+    #[test]
+    fn longest_races() {
+        assert_eq!(
+            Race::new(u64::MAX, 0).winning_window(),
+            Some(1..=u64::MAX - 1)
+        );
+        assert_eq!(
+            winning_window(u64::MAX, 49, |charge| charge as u128),
+            Some(50..=u64::MAX)
+        );
+        let phase = Phase {
+            duration: u64::MAX,
+            profile: Profile::Linear,
+        };
+        let race = Race {
+            phases: vec![phase, phase],
+            ..Race::new(0, 0)
+        };
+        assert_eq!(race.duration(), u64::MAX);
+        assert_eq!(race.distance(1), (u64::MAX - 1) as u128);
+    }
+}
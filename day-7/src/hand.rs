@@ -0,0 +1,548 @@
+// Advent of Code - Day 7: Camel Cards Hand evaluation
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ranking::Ranking;
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub(crate) enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl From<char> for Rank {
+    fn from(value: char) -> Self {
+        match value {
+            '2' => Rank::Two,
+            '3' => Rank::Three,
+            '4' => Rank::Four,
+            '5' => Rank::Five,
+            '6' => Rank::Six,
+            '7' => Rank::Seven,
+            '8' => Rank::Eight,
+            '9' => Rank::Nine,
+            'T' => Rank::Ten,
+            'J' => Rank::Jack,
+            'Q' => Rank::Queen,
+            'K' => Rank::King,
+            'A' => Rank::Ace,
+            _ => panic!("Invalid card: {}", value),
+        }
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels = "23456789TJQKA".as_bytes();
+        write!(f, "{}", labels[*self as usize] as char)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub(crate) enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    fn parse(value: char) -> Option<Self> {
+        match value {
+            'c' => Some(Suit::Clubs),
+            'd' => Some(Suit::Diamonds),
+            'h' => Some(Suit::Hearts),
+            's' => Some(Suit::Spades),
+            _ => None,
+        }
+    }
+}
+
+/// Card with an optional suit, the puzzle's cards have none
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub(crate) struct Card {
+    pub(crate) rank: Rank,
+    pub(crate) suit: Option<Suit>,
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.rank)?;
+        match self.suit {
+            Some(Suit::Clubs) => write!(f, "c"),
+            Some(Suit::Diamonds) => write!(f, "d"),
+            Some(Suit::Hearts) => write!(f, "h"),
+            Some(Suit::Spades) => write!(f, "s"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Write `cards` the way they are parsed
+pub(crate) fn format_cards(cards: &[Card]) -> String {
+    cards.iter().map(Card::to_string).collect()
+}
+
+/// Parse cards written as their rank, optionally followed by a suit, e.g. `KK677` or `KhKs6c7d7h`
+pub(crate) fn parse_cards(cards: &str) -> Vec<Card> {
+    let mut chars = cards.chars().peekable();
+    let mut parsed = Vec::new();
+    while let Some(rank) = chars.next() {
+        let suit = chars.peek().copied().and_then(Suit::parse);
+        if suit.is_some() {
+            chars.next();
+        }
+        parsed.push(Card {
+            rank: Rank::from(rank),
+            suit,
+        });
+    }
+    parsed
+}
+
+/// Hand category, the strongest one a hand satisfies is its type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Category {
+    pub(crate) name: String,
+    /// Groups of equal ranks the hand needs, largest first, each needed group
+    /// is covered by a distinct group of the hand that is at least as large
+    pub(crate) groups: Vec<u32>,
+    /// Whether all cards need to have the same suit
+    pub(crate) suited: bool,
+}
+
+impl Category {
+    /// Whether a hand with `groups`, largest first, and `wildcards` fits this category
+    ///
+    /// Pairing the needed groups with the hand's groups by size minimises the
+    /// number of wildcards required to make up the difference.
+    fn matches(&self, groups: &[u32], wildcards: u32, suited: bool) -> bool {
+        let missing: u32 = self
+            .groups
+            .iter()
+            .enumerate()
+            .map(|(idx, need)| need.saturating_sub(groups.get(idx).copied().unwrap_or(0)))
+            .sum();
+        (suited || !self.suited) && missing <= wildcards
+    }
+}
+
+impl From<&str> for Category {
+    /// Parse `<name>[:<group sizes>[:suited]]`, e.g. `full house:3,2` or `flush::suited`
+    fn from(category: &str) -> Self {
+        let mut parts = category.split(':');
+        let name = parts
+            .next()
+            .expect("Category should have a name.")
+            .to_string();
+        let mut groups: Vec<u32> = parts
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .filter(|size| !size.is_empty())
+            .map(|size| size.parse().expect("Group size should be a number."))
+            .collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        let suited = match parts.next() {
+            None => false,
+            Some("suited") => true,
+            Some(other) => panic!("Invalid category flag: `{other}`"),
+        };
+        Category {
+            name,
+            groups,
+            suited,
+        }
+    }
+}
+
+/// The puzzle's categories from weakest to strongest
+fn standard_categories() -> Vec<Category> {
+    [
+        "high card",
+        "one pair:2",
+        "two pair:2,2",
+        "three of a kind:3",
+        "full house:3,2",
+        "four of a kind:4",
+        "five of a kind:5",
+    ]
+    .into_iter()
+    .map(Category::from)
+    .collect()
+}
+
+/// How hands of the same type are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TieBreak {
+    /// Compare the cards in the order they were dealt, as in the puzzle
+    Positional,
+    /// Compare the strongest cards first, as in poker
+    Descending,
+}
+
+/// How wildcards count towards the category of a hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Wildcards {
+    /// Wildcards complete whichever category makes the hand strongest, as in the puzzle
+    Best,
+    /// Wildcards only match each other, forming a group of their own
+    OwnGroup,
+}
+
+/// Rules that decide how hands are ranked against each other
+#[derive(Debug, Clone)]
+pub(crate) struct Ruleset {
+    /// Number of cards in a hand
+    pub(crate) hand_size: usize,
+    /// All ranks from weakest to strongest
+    pub(crate) order: Vec<Rank>,
+    /// Ranks that act like whatever card makes the hand strongest
+    pub(crate) wild: Vec<Rank>,
+    pub(crate) wildcards: Wildcards,
+    /// Hand categories from weakest to strongest
+    pub(crate) categories: Vec<Category>,
+    pub(crate) tie_break: TieBreak,
+}
+
+impl Default for Ruleset {
+    /// The rules of part one: no wildcards and the Jack between Ten and Queen
+    fn default() -> Self {
+        Ruleset {
+            hand_size: 5,
+            order: "23456789TJQKA".chars().map(Rank::from).collect(),
+            wild: Vec::new(),
+            wildcards: Wildcards::Best,
+            categories: standard_categories(),
+            tie_break: TieBreak::Positional,
+        }
+    }
+}
+
+impl Ruleset {
+    /// The rules of part two: Jacks are wild Jokers and the weakest card
+    pub(crate) fn jokers() -> Self {
+        Ruleset {
+            order: "J23456789TQKA".chars().map(Rank::from).collect(),
+            wild: vec![Rank::Jack],
+            ..Default::default()
+        }
+    }
+
+    fn strength(&self, rank: Rank) -> usize {
+        self.order
+            .iter()
+            .position(|&r| r == rank)
+            .unwrap_or_else(|| panic!("Card order should contain {rank:?}"))
+    }
+
+    /// Name of the category of `hand`
+    pub(crate) fn type_name(&self, hand: &Hand) -> &str {
+        &self.categories[hand.hand_type].name
+    }
+}
+
+impl From<&str> for Ruleset {
+    /// Parse `;`-separated settings on top of the part one rules, e.g.
+    /// `size=6;order=J23456789TQKA;wild=J;wildcards=own;tiebreak=descending;categories=high card|pair:2`
+    /// with the categories separated by `|` from weakest to strongest
+    fn from(rules: &str) -> Self {
+        let mut ruleset = Ruleset::default();
+        for setting in rules.split(';').filter(|setting| !setting.is_empty()) {
+            match setting.split_once('=') {
+                Some(("size", size)) => {
+                    ruleset.hand_size = size.parse().expect("Hand size should be a number.")
+                }
+                Some(("order", order)) => ruleset.order = order.chars().map(Rank::from).collect(),
+                Some(("wild", wild)) => ruleset.wild = wild.chars().map(Rank::from).collect(),
+                Some(("categories", categories)) => {
+                    ruleset.categories = categories.split('|').map(Category::from).collect()
+                }
+                Some(("wildcards", "best")) => ruleset.wildcards = Wildcards::Best,
+                Some(("wildcards", "own")) => ruleset.wildcards = Wildcards::OwnGroup,
+                Some(("tiebreak", "positional")) => ruleset.tie_break = TieBreak::Positional,
+                Some(("tiebreak", "descending")) => ruleset.tie_break = TieBreak::Descending,
+                _ => panic!("Invalid setting: `{setting}`"),
+            }
+        }
+        // Without a category free of groups and suits some hands would have no type
+        assert!(
+            ruleset
+                .categories
+                .iter()
+                .any(|category| category.groups.is_empty() && !category.suited),
+            "Categories should include a catch-all without groups, like `high card`."
+        );
+        ruleset
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Hand {
+    pub(crate) cards: Vec<Card>,
+    /// Index into `Ruleset::categories`
+    pub(crate) hand_type: usize,
+    /// The cards with every wildcard replaced by the card it stands in for
+    pub(crate) substitution: Vec<Card>,
+    /// Positions of the cards in tie-break order
+    tie_break: Vec<usize>,
+    /// Strength of each card in tie-break order
+    strengths: Vec<usize>,
+    pub(crate) bid: u32,
+}
+
+impl Hand {
+    /// Parse a hand and its bid, evaluating it with `ruleset`
+    pub(crate) fn parse(line: &str, ruleset: &Ruleset) -> Self {
+        let (cards, bid) = line
+            .split_once(' ')
+            .expect("Hand should be followed by a bid.");
+        let cards = parse_cards(cards);
+        assert_eq!(
+            cards.len(),
+            ruleset.hand_size,
+            "Hand should contain {} cards.",
+            ruleset.hand_size
+        );
+        let bid = bid.trim().parse().expect("Bid should be a number.");
+        Hand::new(&cards, bid, ruleset)
+    }
+
+    pub(crate) fn new(cards: &[Card], bid: u32, ruleset: &Ruleset) -> Self {
+        let is_wild = |card: &&Card| ruleset.wild.contains(&card.rank);
+        let counts: HashMap<Rank, u32> =
+            cards
+                .iter()
+                .filter(|card| !is_wild(card))
+                .fold(HashMap::new(), |mut counts, card| {
+                    *counts.entry(card.rank).or_insert(0) += 1;
+                    counts
+                });
+        let mut groups: Vec<(Rank, u32)> = counts.into_iter().collect();
+        groups.sort_unstable_by_key(|&(rank, size)| Reverse((size, ruleset.strength(rank))));
+        let mut sizes: Vec<u32> = groups.iter().map(|&(_, size)| size).collect();
+        let mut wildcards = cards.iter().filter(is_wild).count() as u32;
+        if ruleset.wildcards == Wildcards::OwnGroup && wildcards > 0 {
+            sizes.push(wildcards);
+            sizes.sort_unstable_by(|a, b| b.cmp(a));
+            wildcards = 0;
+        }
+
+        // Wildcards take the suit of the other cards
+        let mut suits = cards
+            .iter()
+            .filter(|card| !is_wild(card))
+            .map(|card| card.suit);
+        let suited = match suits.next() {
+            Some(first) => first.is_some() && suits.all(|suit| suit == first),
+            None => true,
+        };
+        let hand_type = ruleset
+            .categories
+            .iter()
+            .rposition(|category| category.matches(&sizes, wildcards, suited))
+            .unwrap_or_else(|| panic!("Invalid count: `{sizes:?}`"));
+        let substitution = match wildcards {
+            0 => cards.to_vec(),
+            _ => substitute(cards, &groups, &ruleset.categories[hand_type], ruleset),
+        };
+
+        let mut tie_break: Vec<usize> = (0..cards.len()).collect();
+        if ruleset.tie_break == TieBreak::Descending {
+            tie_break.sort_by_key(|&idx| Reverse(ruleset.strength(cards[idx].rank)));
+        }
+        let strengths = tie_break
+            .iter()
+            .map(|&idx| ruleset.strength(cards[idx].rank))
+            .collect();
+        Hand {
+            cards: cards.to_vec(),
+            hand_type,
+            substitution,
+            tie_break,
+            strengths,
+            bid,
+        }
+    }
+
+    /// First cards, in tie-break order, on which the two hands differ in strength,
+    /// together with their position in tie-break order
+    pub(crate) fn deciding_cards(&self, other: &Hand) -> Option<(usize, Card, Card)> {
+        let idx = self
+            .strengths
+            .iter()
+            .zip(&other.strengths)
+            .position(|(a, b)| a != b)?;
+        Some((
+            idx,
+            self.cards[self.tie_break[idx]],
+            other.cards[other.tie_break[idx]],
+        ))
+    }
+
+    pub(crate) fn winnings(&self, rank: u32) -> u32 {
+        self.bid * rank
+    }
+}
+
+/// Replace the wildcards in `cards` so that they form `category`
+///
+/// The wildcards fill up the groups of `category` paired by size with the
+/// `groups` of the other cards, strongest ranks first. Wildcards that are not
+/// needed join the largest group, and they all take the suit of the first other card.
+fn substitute(
+    cards: &[Card],
+    groups: &[(Rank, u32)],
+    category: &Category,
+    ruleset: &Ruleset,
+) -> Vec<Card> {
+    let is_wild = |card: &Card| ruleset.wild.contains(&card.rank);
+    let mut unused =
+        ruleset.order.iter().rev().copied().filter(|rank| {
+            !ruleset.wild.contains(rank) && !groups.iter().any(|&(r, _)| r == *rank)
+        });
+    let mut targets: Vec<Rank> = Vec::new();
+    for (idx, &need) in category.groups.iter().enumerate() {
+        let (rank, size) = match groups.get(idx) {
+            Some(&group) => group,
+            None => match unused.next() {
+                Some(rank) => (rank, 0),
+                None => break,
+            },
+        };
+        targets.extend(std::iter::repeat_n(
+            rank,
+            need.saturating_sub(size) as usize,
+        ));
+    }
+    let largest = groups
+        .first()
+        .map(|&(rank, _)| rank)
+        .or(targets.first().copied())
+        .or(unused.next());
+    let suit = cards
+        .iter()
+        .find(|card| !is_wild(card))
+        .and_then(|card| card.suit);
+    let mut targets = targets.into_iter();
+    cards
+        .iter()
+        .map(|&card| {
+            if !is_wild(&card) {
+                return card;
+            }
+            match targets.next().or(largest) {
+                Some(rank) => Card {
+                    rank,
+                    suit: suit.or(card.suit),
+                },
+                None => card,
+            }
+        })
+        .collect()
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Hand {}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.hand_type
+            .cmp(&other.hand_type)
+            .then_with(|| self.strengths.cmp(&other.strengths))
+    }
+}
+
+/// Sum of the bids of all hands multiplied by their rank under `ruleset`
+pub(crate) fn total_winnings(input: &str, ruleset: &Ruleset) -> u32 {
+    Ranking::new(input, ruleset).total()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hand::{total_winnings, Hand, Ruleset, TieBreak, Wildcards};
+
+    #[test]
+    fn ruleset_from_settings() {
+        let ruleset = Ruleset::from("order=J23456789TQKA;wild=J");
+        assert_eq!(ruleset.order, Ruleset::jokers().order);
+        assert_eq!(ruleset.wild, Ruleset::jokers().wild);
+        let example_input = include_str!("./example.txt");
+        assert_eq!(total_winnings(example_input, &ruleset), 5905);
+    }
+
+    #[test]
+    fn descending_tie_break() {
+        let ruleset = Ruleset {
+            tie_break: TieBreak::Descending,
+            ..Default::default()
+        };
+        let low = Hand::parse("2AKQ3 1", &ruleset);
+        let high = Hand::parse("A2KQ4 1", &ruleset);
+        assert_eq!(ruleset.type_name(&low), "high card");
+        assert!(low < high);
+        assert!(Hand::parse("3AKQ2 1", &ruleset) == Hand::parse("2AKQ3 1", &ruleset));
+    }
+
+    #[test]
+    fn wildcards_as_own_group() {
+        let ruleset = Ruleset {
+            wildcards: Wildcards::OwnGroup,
+            ..Ruleset::jokers()
+        };
+        let type_name = |cards: &str, ruleset: &Ruleset| {
+            let hand = Hand::parse(&format!("{cards} 1"), ruleset);
+            ruleset.type_name(&hand).to_string()
+        };
+        assert_eq!(type_name("KTJJT", &ruleset), "two pair");
+        assert_eq!(type_name("KTJJT", &Ruleset::jokers()), "four of a kind");
+        assert_eq!(type_name("JJJJJ", &Ruleset::jokers()), "five of a kind");
+    }
+
+    #[test]
+    fn homebrew_categories() {
+        let ruleset = Ruleset::from(
+            "size=6;wild=J;categories=high card|pair:2|flush::suited|three pair:2,2,2|double triple:3,3",
+        );
+        let type_name = |cards: &str| {
+            let hand = Hand::parse(&format!("{cards} 1"), &ruleset);
+            ruleset.type_name(&hand).to_string()
+        };
+        assert_eq!(type_name("223344"), "three pair");
+        assert_eq!(type_name("22334J"), "three pair");
+        assert_eq!(type_name("222333"), "double triple");
+        assert_eq!(type_name("2h5h9hKhAhJc"), "flush");
+        assert_eq!(type_name("2h5h9hKhAs3h"), "high card");
+        assert_eq!(type_name("2h2c9hKhAs3h"), "pair");
+    }
+
+    #[test]
+    #[should_panic(expected = "catch-all")]
+    fn categories_without_catch_all() {
+        let _ = Ruleset::from("categories=pair:2|three of a kind:3");
+    }
+}
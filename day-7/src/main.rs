@@ -1,13 +1,63 @@
 // Advent of Code - Day 7: Camel Cards
 
+pub mod hand;
 pub mod part1;
 pub mod part2;
+pub mod ranking;
+
+use hand::{parse_cards, total_winnings, Hand, Ruleset};
+use ranking::{explain, Ranking};
 
 const INPUT: &str = include_str!("./input.txt");
 
+/// Evaluate the hand given on the command line, exiting if it has the wrong number of cards
+fn single_hand(cards: &str, ruleset: &Ruleset) -> Hand {
+    let parsed = parse_cards(cards);
+    if parsed.len() != ruleset.hand_size {
+        eprintln!("Hand `{cards}` should contain {} cards", ruleset.hand_size);
+        std::process::exit(2);
+    }
+    Hand::new(&parsed, 0, ruleset)
+}
+
 fn main() {
-    let part_one_answer = part1::solve_part_one(INPUT);
-    println!("Part one: {part_one_answer:?}");
-    let part_two_answer = part2::solve_part_two(INPUT);
-    println!("Part two: {part_two_answer:?}");
+    // `day-7 --rules <settings>` ranks the hands with a custom ruleset, e.g.
+    // `size=6;wild=J;categories=high card|pair:2|three pair:2,2,2`, and
+    // `day-7 --type <settings> <cards>` prints the category of a single hand,
+    // `day-7 --table <settings>` prints the ranked hands and
+    // `day-7 --explain <settings> <cards> <cards>` explains which of two hands wins
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer = part1::solve_part_one(INPUT);
+            println!("Part one: {part_one_answer:?}");
+            let part_two_answer = part2::solve_part_two(INPUT);
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["--rules", rules] => {
+            let answer = total_winnings(INPUT, &Ruleset::from(rules));
+            println!("Total winnings: {answer:?}");
+        }
+        ["--type", rules, cards] => {
+            let ruleset = Ruleset::from(rules);
+            let hand = single_hand(cards, &ruleset);
+            println!("{cards}: {}", ruleset.type_name(&hand));
+        }
+        ["--table", rules] => {
+            let ruleset = Ruleset::from(rules);
+            println!("{}", Ranking::new(INPUT, &ruleset));
+        }
+        ["--explain", rules, a, b] => {
+            let ruleset = Ruleset::from(rules);
+            let hand = |cards| single_hand(cards, &ruleset);
+            println!("{}", explain(&hand(a), &hand(b), &ruleset));
+        }
+        _ => {
+            eprintln!(
+                "Usage: day-7 [--rules <settings> | --type <settings> <cards> | --table <settings> \
+                 | --explain <settings> <cards> <cards>]"
+            );
+            std::process::exit(2);
+        }
+    }
 }
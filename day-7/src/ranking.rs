@@ -0,0 +1,202 @@
+// Advent of Code - Day 7: Camel Cards Ranking
+
+use std::fmt;
+
+use crate::hand::{format_cards, Hand, Ruleset, TieBreak};
+
+/// Hand together with its place in the ranking
+#[derive(Debug, Clone)]
+pub(crate) struct RankedHand {
+    pub(crate) rank: u32,
+    pub(crate) hand: Hand,
+    pub(crate) winnings: u32,
+}
+
+/// All hands ranked from weakest to strongest under a ruleset
+pub(crate) struct Ranking<'a> {
+    pub(crate) ruleset: &'a Ruleset,
+    pub(crate) hands: Vec<RankedHand>,
+}
+
+impl<'a> Ranking<'a> {
+    pub(crate) fn new(input: &str, ruleset: &'a Ruleset) -> Self {
+        let mut hands: Vec<Hand> = input
+            .lines()
+            .map(|line| Hand::parse(line, ruleset))
+            .collect();
+        hands.sort();
+        let hands = hands
+            .into_iter()
+            .zip(1..)
+            .map(|(hand, rank)| RankedHand {
+                rank,
+                winnings: hand.winnings(rank),
+                hand,
+            })
+            .collect();
+        Ranking { ruleset, hands }
+    }
+
+    /// Sum of the winnings of all hands
+    pub(crate) fn total(&self) -> u32 {
+        self.hands.iter().map(|ranked| ranked.winnings).sum()
+    }
+}
+
+impl fmt::Display for Ranking<'_> {
+    /// Table of all hands from weakest to strongest
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cards_width = self
+            .hands
+            .iter()
+            .map(|ranked| format_cards(&ranked.hand.cards).len())
+            .max()
+            .unwrap_or(0)
+            .max("plays as".len());
+        let type_width = self
+            .ruleset
+            .categories
+            .iter()
+            .map(|category| category.name.len())
+            .max()
+            .unwrap_or(0);
+        write!(
+            f,
+            "{:>6} {:>cards_width$} {:>type_width$} {:>cards_width$} {:>6} {:>10}",
+            "rank", "hand", "type", "plays as", "bid", "winnings"
+        )?;
+        for ranked in &self.hands {
+            write!(
+                f,
+                "\n{:>6} {:>cards_width$} {:>type_width$} {:>cards_width$} {:>6} {:>10}",
+                ranked.rank,
+                format_cards(&ranked.hand.cards),
+                self.ruleset.type_name(&ranked.hand),
+                format_cards(&ranked.hand.substitution),
+                ranked.hand.bid,
+                ranked.winnings
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Hand with its type and, if wildcards changed it, the hand it plays as
+fn describe(hand: &Hand, ruleset: &Ruleset) -> String {
+    let cards = format_cards(&hand.cards);
+    let substitution = format_cards(&hand.substitution);
+    if cards == substitution {
+        format!("{cards} ({})", ruleset.type_name(hand))
+    } else {
+        format!("{cards} ({} as {substitution})", ruleset.type_name(hand))
+    }
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+/// Explain why one of the hands beats the other, or why they tie
+pub(crate) fn explain(a: &Hand, b: &Hand, ruleset: &Ruleset) -> String {
+    let (winner, loser) = if a >= b { (a, b) } else { (b, a) };
+    let (winner_text, loser_text) = (describe(winner, ruleset), describe(loser, ruleset));
+    if winner.hand_type != loser.hand_type {
+        return format!(
+            "{winner_text} beats {loser_text}: {} ranks above {}",
+            ruleset.type_name(winner),
+            ruleset.type_name(loser)
+        );
+    }
+    let Some((idx, winning_card, losing_card)) = winner.deciding_cards(loser) else {
+        return format!("{winner_text} ties with {loser_text}: same type and equally strong cards");
+    };
+    let position = match ruleset.tie_break {
+        TieBreak::Positional => format!("{} card", ordinal(idx + 1)),
+        TieBreak::Descending => format!("{} strongest card", ordinal(idx + 1)),
+    };
+    let wild_note = if [winning_card, losing_card]
+        .iter()
+        .any(|card| ruleset.wild.contains(&card.rank))
+    {
+        ", wildcards keep their own rank in tie-breaks"
+    } else {
+        ""
+    };
+    format!(
+        "{winner_text} beats {loser_text}: both are {}, the {position} decides and \
+         {winning_card} is stronger than {losing_card}{wild_note}",
+        ruleset.type_name(winner)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hand::{format_cards, parse_cards, Hand, Ruleset};
+    use crate::ranking::{explain, Ranking};
+
+    #[test]
+    fn ranked_table() {
+        let ruleset = Ruleset::jokers();
+        let ranking = Ranking::new(include_str!("./example.txt"), &ruleset);
+        let rows: Vec<(u32, String, &str, String, u32)> = ranking
+            .hands
+            .iter()
+            .map(|ranked| {
+                (
+                    ranked.rank,
+                    format_cards(&ranked.hand.cards),
+                    ruleset.type_name(&ranked.hand),
+                    format_cards(&ranked.hand.substitution),
+                    ranked.winnings,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows[2..],
+            [
+                (3, "T55J5".into(), "four of a kind", "T5555".into(), 2052),
+                (4, "QQQJA".into(), "four of a kind", "QQQQA".into(), 1932),
+                (5, "KTJJT".into(), "four of a kind", "KTTTT".into(), 1100),
+            ]
+        );
+        assert_eq!(ranking.total(), 5905);
+    }
+
+    #[test]
+    fn explanations() {
+        let hand = |cards: &str, ruleset: &Ruleset| Hand::new(&parse_cards(cards), 0, ruleset);
+        let ruleset = Ruleset::default();
+        assert_eq!(
+            explain(&hand("KTJJT", &ruleset), &hand("KK677", &ruleset), &ruleset),
+            "KK677 (two pair) beats KTJJT (two pair): both are two pair, \
+             the 2nd card decides and K is stronger than T"
+        );
+        let ruleset = Ruleset::jokers();
+        assert_eq!(
+            explain(&hand("KK677", &ruleset), &hand("KTJJT", &ruleset), &ruleset),
+            "KTJJT (four of a kind as KTTTT) beats KK677 (two pair): \
+             four of a kind ranks above two pair"
+        );
+        assert_eq!(
+            explain(&hand("JKKK2", &ruleset), &hand("QQQQ2", &ruleset), &ruleset),
+            "QQQQ2 (four of a kind) beats JKKK2 (four of a kind as KKKK2): both are \
+             four of a kind, the 1st card decides and Q is stronger than J, \
+             wildcards keep their own rank in tie-breaks"
+        );
+    }
+
+    #[test]
+    fn substitution_for_homebrew_categories() {
+        let ruleset = Ruleset::from("size=6;wild=J;categories=high card|pair:2|three pair:2,2,2");
+        let hand = Hand::new(&parse_cards("2h3h9hJcJd9s"), 0, &ruleset);
+        assert_eq!(ruleset.type_name(&hand), "three pair");
+        assert_eq!(format_cards(&hand.substitution), "2h3h9h3h2h9s");
+    }
+}
@@ -1,13 +1,46 @@
 // Advent of Code - Day 8: Haunted Wasteland
 
+pub mod network;
 pub mod part1;
 pub mod part2;
 
+use network::{Network, NetworkError};
+
 const INPUT: &str = include_str!("./input.txt");
 
+fn invalid_network(err: NetworkError) -> ! {
+    eprintln!("Invalid network: {err}");
+    std::process::exit(1);
+}
+
 fn main() {
-    let part_one_answer = part1::solve_part_one(INPUT);
-    println!("Part one: {part_one_answer:?}");
-    let part_two_answer = part2::solve_part_two(INPUT);
-    println!("Part two: {part_two_answer:?}");
+    // `day-8 --analyse` reports unreachable nodes, self loops and the cyclic
+    // components the ghosts visit, `day-8 --dot` prints the network as Graphviz DOT
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            match part1::solve_part_one(INPUT) {
+                Ok(Some(part_one_answer)) => println!("Part one: {part_one_answer:?}"),
+                Ok(None) => println!("Part one: the instructions never lead from AAA to ZZZ"),
+                Err(err) => invalid_network(err),
+            }
+            match part2::solve_part_two(INPUT) {
+                Ok(Some(part_two_answer)) => println!("Part two: {part_two_answer:?}"),
+                Ok(None) => println!("Part two: the ghosts are never all on a `..Z` node at once"),
+                Err(err) => invalid_network(err),
+            }
+        }
+        ["--analyse"] => match Network::parse(INPUT) {
+            Ok(network) => println!("{}", network.analyse()),
+            Err(err) => invalid_network(err),
+        },
+        ["--dot"] => match Network::parse(INPUT) {
+            Ok(network) => print!("{}", network.to_dot()),
+            Err(err) => invalid_network(err),
+        },
+        _ => {
+            eprintln!("Usage: day-8 [--analyse | --dot]");
+            std::process::exit(2);
+        }
+    }
 }
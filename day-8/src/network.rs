@@ -0,0 +1,382 @@
+// Advent of Code - Day 8: Haunted Wasteland Network
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::{self, Write};
+
+/// Instructions and the `node = (left, right)` map of the desert
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Network<'a> {
+    pub(crate) instructions: Vec<char>,
+    pub(crate) nodes: BTreeMap<&'a str, (&'a str, &'a str)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NetworkError {
+    /// The first line contains no instructions
+    MissingInstructions,
+    /// An instruction other than `L` or `R`
+    InvalidInstruction(char),
+    /// The instructions are not followed by a blank line
+    MissingBlankLine,
+    /// A line, counted from one, that is not formatted as `AAA = (BBB, CCC)`
+    MalformedLine(usize),
+    DuplicateNode(String),
+    /// `node` leads to `neighbour`, which is never defined
+    UndefinedNode {
+        node: String,
+        neighbour: String,
+    },
+    /// A node the puzzle needs, like `AAA` or `ZZZ`, is never defined
+    MissingNode(String),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::MissingInstructions => write!(f, "the first line has no instructions"),
+            NetworkError::InvalidInstruction(ch) => write!(f, "invalid instruction `{ch}`"),
+            NetworkError::MissingBlankLine => write!(f, "line 2 should be blank"),
+            NetworkError::MalformedLine(line) => write!(f, "line {line} is not a node"),
+            NetworkError::DuplicateNode(node) => write!(f, "node {node} is defined twice"),
+            NetworkError::UndefinedNode { node, neighbour } => {
+                write!(f, "node {node} leads to undefined node {neighbour}")
+            }
+            NetworkError::MissingNode(node) => write!(f, "node {node} is not defined"),
+        }
+    }
+}
+
+impl<'a> Network<'a> {
+    /// Parse and validate the instructions and the network
+    pub(crate) fn parse(input: &'a str) -> Result<Self, NetworkError> {
+        let mut lines = input.lines();
+        let instructions: Vec<char> = lines.next().unwrap_or_default().trim().chars().collect();
+        if instructions.is_empty() {
+            return Err(NetworkError::MissingInstructions);
+        }
+        if let Some(&ch) = instructions.iter().find(|&&ch| ch != 'L' && ch != 'R') {
+            return Err(NetworkError::InvalidInstruction(ch));
+        }
+
+        if !lines.next().unwrap_or_default().trim().is_empty() {
+            return Err(NetworkError::MissingBlankLine);
+        }
+
+        let mut nodes = BTreeMap::new();
+        for (idx, line) in lines.enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (node, neighbours) = line
+                .split_once(" = ")
+                .and_then(|(node, neighbours)| {
+                    let neighbours = neighbours.strip_prefix('(')?.strip_suffix(')')?;
+                    Some((node, neighbours.split_once(", ")?))
+                })
+                .ok_or(NetworkError::MalformedLine(idx + 3))?;
+            if nodes.insert(node, neighbours).is_some() {
+                return Err(NetworkError::DuplicateNode(node.to_string()));
+            }
+        }
+        for (node, (left, right)) in &nodes {
+            if let Some(neighbour) = [left, right].into_iter().find(|n| !nodes.contains_key(*n)) {
+                return Err(NetworkError::UndefinedNode {
+                    node: node.to_string(),
+                    neighbour: neighbour.to_string(),
+                });
+            }
+        }
+        Ok(Network {
+            instructions,
+            nodes,
+        })
+    }
+
+    /// Check that `node` is defined
+    pub(crate) fn ensure_defined(&self, node: &str) -> Result<(), NetworkError> {
+        match self.nodes.contains_key(node) {
+            true => Ok(()),
+            false => Err(NetworkError::MissingNode(node.to_string())),
+        }
+    }
+
+    /// Node reached from `node` by following `instruction`
+    pub(crate) fn step(&self, node: &str, instruction: char) -> &'a str {
+        match instruction {
+            'L' => self.nodes[node].0,
+            _ => self.nodes[node].1,
+        }
+    }
+
+    /// Neighbours of `node` along the directions that occur in the instructions
+    fn neighbours(&self, node: &str) -> impl Iterator<Item = &'a str> + '_ {
+        let (left, right) = self.nodes[node];
+        [('L', left), ('R', right)]
+            .into_iter()
+            .filter(|(instruction, _)| self.instructions.contains(instruction))
+            .map(|(_, neighbour)| neighbour)
+    }
+
+    /// The `..A` nodes the ghosts start at, including `AAA`
+    pub(crate) fn starts(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.nodes
+            .keys()
+            .copied()
+            .filter(|node| node.ends_with('A'))
+    }
+
+    /// Nodes visited by following the instructions from every start until
+    /// the walk repeats a `(node, instruction index)` state
+    fn visited(&self) -> BTreeSet<&'a str> {
+        let mut states: HashSet<(&str, usize)> = HashSet::new();
+        for start in self.starts() {
+            let mut node = start;
+            for idx in (0..self.instructions.len()).cycle() {
+                if !states.insert((node, idx)) {
+                    break;
+                }
+                node = self.step(node, self.instructions[idx]);
+            }
+        }
+        states.into_iter().map(|(node, _)| node).collect()
+    }
+
+    /// Summarise the structure of the network
+    pub(crate) fn analyse(&self) -> Analysis<'a> {
+        let mut reachable: BTreeSet<&str> = self.starts().collect();
+        let mut stack: Vec<&str> = reachable.iter().copied().collect();
+        while let Some(node) = stack.pop() {
+            for neighbour in self.neighbours(node) {
+                if reachable.insert(neighbour) {
+                    stack.push(neighbour);
+                }
+            }
+        }
+        let unreachable = self
+            .nodes
+            .keys()
+            .copied()
+            .filter(|node| !reachable.contains(node))
+            .collect();
+        let self_loops = self
+            .nodes
+            .iter()
+            .filter(|(node, (left, right))| left == *node && right == *node)
+            .map(|(node, _)| *node)
+            .collect();
+        Analysis {
+            unreachable,
+            self_loops,
+            components: self.cyclic_components(&self.visited()),
+        }
+    }
+
+    /// Strongly connected components among `nodes` that contain a cycle,
+    /// found with Tarjan's algorithm
+    fn cyclic_components(&self, nodes: &BTreeSet<&'a str>) -> Vec<Vec<&'a str>> {
+        #[derive(Default)]
+        struct Tarjan<'a> {
+            index: BTreeMap<&'a str, usize>,
+            low_link: BTreeMap<&'a str, usize>,
+            stack: Vec<&'a str>,
+            on_stack: HashSet<&'a str>,
+            components: Vec<Vec<&'a str>>,
+        }
+
+        fn connect<'a>(
+            network: &Network<'a>,
+            nodes: &BTreeSet<&'a str>,
+            tarjan: &mut Tarjan<'a>,
+            node: &'a str,
+        ) {
+            let index = tarjan.index.len();
+            tarjan.index.insert(node, index);
+            tarjan.low_link.insert(node, index);
+            tarjan.stack.push(node);
+            tarjan.on_stack.insert(node);
+            for neighbour in network.neighbours(node).filter(|n| nodes.contains(n)) {
+                if !tarjan.index.contains_key(neighbour) {
+                    connect(network, nodes, tarjan, neighbour);
+                    let low_link = tarjan.low_link[node].min(tarjan.low_link[neighbour]);
+                    tarjan.low_link.insert(node, low_link);
+                } else if tarjan.on_stack.contains(neighbour) {
+                    let low_link = tarjan.low_link[node].min(tarjan.index[neighbour]);
+                    tarjan.low_link.insert(node, low_link);
+                }
+            }
+            if tarjan.low_link[node] == index {
+                let mut component = Vec::new();
+                while let Some(member) = tarjan.stack.pop() {
+                    tarjan.on_stack.remove(member);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                tarjan.components.push(component);
+            }
+        }
+
+        let mut tarjan = Tarjan::default();
+        for &node in nodes {
+            if !tarjan.index.contains_key(node) {
+                connect(self, nodes, &mut tarjan, node);
+            }
+        }
+        let mut components: Vec<Vec<&str>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.neighbours(component[0]).any(|n| n == component[0])
+            })
+            .collect();
+        components.sort_unstable();
+        components
+    }
+
+    /// Export the network as a Graphviz DOT graph, with the start nodes in
+    /// green, the goal nodes in red and edges labelled by their instruction
+    pub(crate) fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n");
+        for (node, (left, right)) in &self.nodes {
+            let colour = if node.ends_with('A') {
+                Some("palegreen")
+            } else if node.ends_with('Z') {
+                Some("lightcoral")
+            } else {
+                None
+            };
+            if let Some(colour) = colour {
+                writeln!(dot, "    \"{node}\" [style=filled, fillcolor={colour}];").unwrap();
+            }
+            if left == right {
+                writeln!(dot, "    \"{node}\" -> \"{left}\" [label=\"L/R\"];").unwrap();
+            } else {
+                writeln!(dot, "    \"{node}\" -> \"{left}\" [label=\"L\"];").unwrap();
+                writeln!(dot, "    \"{node}\" -> \"{right}\" [label=\"R\"];").unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Structure of a network as seen from the start nodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Analysis<'a> {
+    /// Nodes that no start node can reach
+    pub(crate) unreachable: Vec<&'a str>,
+    /// Dead ends like `XXX = (XXX, XXX)` that can never be left
+    pub(crate) self_loops: Vec<&'a str>,
+    /// Strongly connected components with a cycle that the instructions
+    /// visit from the start nodes, in which the ghosts end up walking in circles
+    pub(crate) components: Vec<Vec<&'a str>>,
+}
+
+impl fmt::Display for Analysis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Unreachable nodes: {}", self.unreachable.join(", "))?;
+        writeln!(f, "Self loops: {}", self.self_loops.join(", "))?;
+        write!(f, "Visited cyclic components:")?;
+        for component in &self.components {
+            let goals = component.iter().filter(|node| node.ends_with('Z')).count();
+            write!(
+                f,
+                "\n    {} ({} nodes, {goals} goals)",
+                component.join(", "),
+                component.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::network::{Network, NetworkError};
+
+    const EXAMPLE_INPUT: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+YYY = (XXX, 11B)";
+
+    #[test]
+    fn analysis() {
+        let analysis = Network::parse(EXAMPLE_INPUT).unwrap().analyse();
+        assert_eq!(analysis.unreachable, vec!["YYY"]);
+        assert_eq!(analysis.self_loops, vec!["XXX"]);
+        assert_eq!(
+            analysis.components,
+            vec![vec!["11B", "11Z"], vec!["22B", "22C", "22Z"]]
+        );
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(
+            Network::parse("LRX\n\nAAA = (AAA, AAA)"),
+            Err(NetworkError::InvalidInstruction('X'))
+        );
+        assert_eq!(
+            Network::parse("LR\n\nAAA = (BBB, AAA)"),
+            Err(NetworkError::UndefinedNode {
+                node: "AAA".to_string(),
+                neighbour: "BBB".to_string()
+            })
+        );
+        assert_eq!(
+            Network::parse("LR\n\nAAA = (AAA, AAA)\nBBB = AAA"),
+            Err(NetworkError::MalformedLine(4))
+        );
+        assert_eq!(
+            Network::parse("\n\nAAA = (AAA, AAA)"),
+            Err(NetworkError::MissingInstructions)
+        );
+        assert_eq!(
+            Network::parse("LR\nAAA = (AAA, AAA)"),
+            Err(NetworkError::MissingBlankLine)
+        );
+        let network = Network::parse("LR\n\nAAA = (AAA, AAA)\n\n").unwrap();
+        assert_eq!(network.nodes.len(), 1);
+        assert_eq!(
+            network.ensure_defined("ZZZ"),
+            Err(NetworkError::MissingNode("ZZZ".to_string()))
+        );
+    }
+
+    #[test]
+    fn dot_export() {
+        let dot = Network::parse("L\n\nAAA = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)")
+            .unwrap()
+            .to_dot();
+        assert_eq!(
+            dot,
+            "digraph network {
+    \"AAA\" [style=filled, fillcolor=palegreen];
+    \"AAA\" -> \"ZZZ\" [label=\"L\"];
+    \"AAA\" -> \"AAA\" [label=\"R\"];
+    \"ZZZ\" [style=filled, fillcolor=lightcoral];
+    \"ZZZ\" -> \"ZZZ\" [label=\"L/R\"];
+}
+"
+        );
+    }
+
+    #[test]
+    fn dot_export_quotes_ids() {
+        // An unquoted DOT ID cannot start with a digit
+        let dot = Network::parse("L\n\n11A = (11Z, 11Z)\n11Z = (11Z, 11Z)")
+            .unwrap()
+            .to_dot();
+        assert!(dot.contains("    \"11A\" [style=filled, fillcolor=palegreen];\n"));
+        assert!(dot.contains("    \"11A\" -> \"11Z\" [label=\"L/R\"];\n"));
+    }
+}
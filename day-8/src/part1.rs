@@ -1,45 +1,27 @@
 // Advent of Code - Day 8: Haunted Wasteland Part 1
 
-use std::collections::BTreeMap;
+use std::collections::HashSet;
 
-pub(crate) fn solve_part_one(input: &str) -> u32 {
-    let mut input = input.lines();
-    let instructions = input.next().unwrap();
-    let network: BTreeMap<&str, (&str, &str)> = input
-        .skip(1)
-        .filter_map(|line| {
-            line.split_once(" = ").map(|(node, neighbours)| {
-                (
-                    node,
-                    neighbours[1..neighbours.len() - 1]
-                        .split_once(", ")
-                        .unwrap(),
-                )
-            })
-        })
-        .collect();
-    instructions
-        .chars()
-        .cycle()
-        .enumerate()
-        .try_fold("AAA", |current_node, (steps, instruction)| {
-            match instruction {
-                'L' => Some(network[current_node].0),
-                'R' => Some(network[current_node].1),
-                _ => None,
-            }
-            .map_or_else(
-                || panic!("Invalid instruction encountered!"),
-                |next_node| {
-                    if next_node == "ZZZ" {
-                        Err(steps as u32 + 1)
-                    } else {
-                        Ok(next_node)
-                    }
-                },
-            )
-        })
-        .unwrap_err()
+use crate::network::{Network, NetworkError};
+
+/// Steps from `AAA` to `ZZZ`, `None` if following the instructions never gets there
+pub(crate) fn solve_part_one(input: &str) -> Result<Option<u32>, NetworkError> {
+    let network = Network::parse(input)?;
+    network.ensure_defined("AAA")?;
+    network.ensure_defined("ZZZ")?;
+    let mut states = HashSet::new();
+    let mut node = "AAA";
+    for (steps, idx) in (0..network.instructions.len()).cycle().enumerate() {
+        // The walk repeats from here on without ever reaching ZZZ
+        if !states.insert((node, idx)) {
+            return Ok(None);
+        }
+        node = network.step(node, network.instructions[idx]);
+        if node == "ZZZ" {
+            return Ok(Some(steps as u32 + 1));
+        }
+    }
+    unreachable!()
 }
 
 #[cfg(test)]
@@ -56,7 +38,7 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)";
         let answer = crate::part1::solve_part_one(example_input);
-        assert_eq!(answer, 2);
+        assert_eq!(answer, Ok(Some(2)));
     }
 
     #[test]
@@ -67,6 +49,17 @@ AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
         let answer = crate::part1::solve_part_one(example_input);
-        assert_eq!(answer, 6);
+        assert_eq!(answer, Ok(Some(6)));
+    }
+
+    #[test]
+    fn part1_never_reaches_goal() {
+        let answer = crate::part1::solve_part_one("L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)");
+        assert_eq!(answer, Ok(None));
+        let answer = crate::part1::solve_part_one("L\n\nZZZ = (ZZZ, ZZZ)");
+        assert_eq!(
+            answer,
+            Err(crate::network::NetworkError::MissingNode("AAA".to_string()))
+        );
     }
 }
@@ -1,68 +1,188 @@
 // Advent of Code - Day 8: Haunted Wasteland Part 2
 
-use std::collections::BTreeMap;
+use std::collections::HashMap;
 
-fn lcm(nums: &[usize]) -> usize {
-    nums.iter().fold(1, |acc, &x| acc * x / gcd(acc, x))
+use crate::network::{Network, NetworkError};
+
+/// Steps at which a ghost stands on a `..Z` node
+///
+/// The state of a ghost is its node together with the index of the next
+/// instruction, so after at most `nodes * instructions` steps it repeats.
+#[derive(Debug, PartialEq, Eq)]
+struct Ghost {
+    /// Steps before the ghost enters its cycle
+    tail: usize,
+    /// Length of the cycle of states
+    cycle: usize,
+    /// Every step below `tail + cycle` at which the ghost is on a `..Z` node
+    hits: Vec<usize>,
 }
 
-fn gcd(a: usize, b: usize) -> usize {
+impl Ghost {
+    fn walk(network: &Network, start: &str) -> Self {
+        let instructions = &network.instructions;
+        let mut seen: HashMap<(&str, usize), usize> = HashMap::new();
+        let mut hits = Vec::new();
+        let mut node = start;
+        for step in 0.. {
+            let idx = step % instructions.len();
+            if let Some(&tail) = seen.get(&(node, idx)) {
+                return Ghost {
+                    tail,
+                    cycle: step - tail,
+                    hits,
+                };
+            }
+            seen.insert((node, idx), step);
+            if node.ends_with('Z') {
+                hits.push(step);
+            }
+            node = network.step(node, instructions[idx]);
+        }
+        unreachable!()
+    }
+
+    fn is_hit(&self, step: usize) -> bool {
+        let step = if step < self.tail {
+            step
+        } else {
+            self.tail + (step - self.tail) % self.cycle
+        };
+        self.hits.binary_search(&step).is_ok()
+    }
+
+    /// Residues modulo the cycle of the steps within the cycle at which the ghost
+    /// is on a `..Z` node, in ascending order
+    fn cycle_hits(&self) -> Vec<u128> {
+        let mut residues: Vec<u128> = self
+            .hits
+            .iter()
+            .filter(|&&hit| hit >= self.tail)
+            .map(|&hit| (hit % self.cycle) as u128)
+            .collect();
+        residues.sort_unstable();
+        residues
+    }
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
     if b == 0 {
-        a
+        (a, 1, 0)
     } else {
-        gcd(b, a % b)
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
     }
 }
 
-pub(crate) fn solve_part_two(input: &str) -> usize {
-    let mut input = input.lines();
-    let instructions = input.next().unwrap();
-    let network: BTreeMap<&str, (&str, &str)> = input
-        .skip(1)
-        .filter_map(|line| {
-            line.split_once(" = ").map(|(node, neighbours)| {
-                (
-                    node,
-                    neighbours[1..neighbours.len() - 1]
-                        .split_once(", ")
-                        .unwrap(),
-                )
-            })
-        })
-        .collect();
-    let starting_nodes = network
-        .keys()
-        .filter(|k| k.ends_with('A'))
-        .cloned()
-        .collect::<Vec<&str>>();
-    let starting_nodes_steps = starting_nodes
-        .iter()
-        .map(|node| {
-            instructions
-                .chars()
-                .cycle()
-                .enumerate()
-                .try_fold(*node, |current_node, (steps, instruction)| {
-                    match instruction {
-                        'L' => Some(network[current_node].0),
-                        'R' => Some(network[current_node].1),
-                        _ => None,
-                    }
-                    .map_or_else(
-                        || panic!("Invalid instruction encountered!"),
-                        |next_node| {
-                            if next_node.ends_with('Z') {
-                                Err(steps + 1)
-                            } else {
-                                Ok(next_node)
-                            }
-                        },
-                    )
+/// Steps at which every ghost combined so far is on a `..Z` node once all of
+/// them are in their cycle, as ascending residues modulo their common period
+#[derive(Debug, PartialEq, Eq)]
+struct Solutions {
+    period: u128,
+    residues: Vec<u128>,
+}
+
+impl Default for Solutions {
+    /// Every step, before any ghost is combined
+    fn default() -> Self {
+        Solutions {
+            period: 1,
+            residues: vec![0],
+        }
+    }
+}
+
+impl Solutions {
+    /// Keep only the solutions at which `ghost` is on a `..Z` node as well,
+    /// `None` if the common period overflows
+    ///
+    /// A solution `t ≡ a (mod period)` and a hit `t ≡ b (mod cycle)` combine
+    /// into `t = a + period * k` with the generalized Chinese remainder theorem
+    /// if `a ≡ b (mod gcd(period, cycle))`. If the new period holds fewer steps
+    /// than there are such pairs, its steps are checked one by one instead, so
+    /// the solution set never grows beyond a single period.
+    fn combine(&self, ghost: &Ghost) -> Option<Solutions> {
+        let cycle = ghost.cycle as u128;
+        let hits = ghost.cycle_hits();
+        // Both fit in an i128 as the cycle of a ghost is a `usize`
+        let (g, inverse, _) = extended_gcd((self.period % cycle) as i128, cycle as i128);
+        let (g, reduced) = (g as u128, cycle / g as u128);
+        let inverse = inverse.rem_euclid(reduced as i128) as u128;
+        let period = (self.period / g).checked_mul(cycle)?;
+
+        let pairs = (self.residues.len() as u128).saturating_mul(hits.len() as u128);
+        let mut residues: Vec<u128> = if period <= pairs {
+            (0..period)
+                .filter(|step| {
+                    self.residues.binary_search(&(step % self.period)).is_ok()
+                        && hits.binary_search(&(step % cycle)).is_ok()
+                })
+                .collect()
+        } else {
+            self.residues
+                .iter()
+                .flat_map(|&a| {
+                    hits.iter().filter_map(move |&b| {
+                        let diff = (b + cycle - a % cycle) % cycle;
+                        // Both factors are below `reduced`, which fits in a u64
+                        let k = diff
+                            .is_multiple_of(g)
+                            .then(|| diff / g * inverse % reduced)?;
+                        Some(a + self.period * k)
+                    })
                 })
-                .unwrap_err()
+                .collect()
+        };
+        residues.sort_unstable();
+        residues.dedup();
+        Some(Solutions { period, residues })
+    }
+}
+
+/// First step at which every ghost is on a `..Z` node, `None` if there is none
+/// or if it does not fit in a `u128`
+///
+/// Below the longest tail the ghosts are checked step by step. From there on
+/// every ghost only repeats its cycle, so the steps at which all of them are on
+/// a goal node are found by combining the ghosts one at a time into the set of
+/// solutions modulo their common period.
+fn first_common_hit(ghosts: &[Ghost]) -> Option<u128> {
+    let longest_tail = ghosts.iter().map(|ghost| ghost.tail).max()?;
+    if let Some(step) =
+        (0..longest_tail).find(|&step| ghosts.iter().all(|ghost| ghost.is_hit(step)))
+    {
+        return Some(step as u128);
+    }
+    let solutions = ghosts
+        .iter()
+        .try_fold(Solutions::default(), |solutions, ghost| {
+            solutions.combine(ghost)
+        })?;
+    let (longest_tail, period) = (longest_tail as u128, solutions.period);
+    solutions
+        .residues
+        .into_iter()
+        .filter_map(|residue| {
+            if residue >= longest_tail {
+                Some(residue)
+            } else {
+                (longest_tail - residue)
+                    .div_ceil(period)
+                    .checked_mul(period)?
+                    .checked_add(residue)
+            }
         })
-        .collect::<Vec<usize>>();
-    lcm(&starting_nodes_steps)
+        .min()
+}
+
+pub(crate) fn solve_part_two(input: &str) -> Result<Option<u128>, NetworkError> {
+    let network = Network::parse(input)?;
+    let ghosts: Vec<Ghost> = network
+        .starts()
+        .map(|node| Ghost::walk(&network, node))
+        .collect();
+    Ok(first_common_hit(&ghosts))
 }
 
 #[cfg(test)]
@@ -80,6 +200,95 @@ mod tests {
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
         let answer = crate::part2::solve_part_two(example_input);
-        assert_eq!(answer, 6);
+        assert_eq!(answer, Ok(Some(6)));
+    }
+
+    #[test]
+    fn part2_offset_cycles() {
+        // The ghosts reach a goal at steps 2, 5, 8, ... and 3, 5, 7, ...
+        let example_input = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11D, 11D)
+11D = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22D, 22D)
+22D = (22Z, 22Z)";
+        let answer = crate::part2::solve_part_two(example_input);
+        assert_eq!(answer, Ok(Some(5)));
+    }
+
+    #[test]
+    fn part2_never_aligned() {
+        // The ghosts reach a goal at odd and at even steps respectively
+        let example_input = "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22B, 22B)";
+        let answer = crate::part2::solve_part_two(example_input);
+        assert_eq!(answer, Ok(None));
+    }
+
+    #[test]
+    fn part2_invalid_network() {
+        let answer = crate::part2::solve_part_two("L\n\n11A = (11Z, 11Z)");
+        assert_eq!(
+            answer,
+            Err(crate::network::NetworkError::UndefinedNode {
+                node: "11A".to_string(),
+                neighbour: "11Z".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn combined_solutions() {
+        use crate::part2::{first_common_hit, Ghost, Solutions};
+
+        let ghost = |cycle, hits| Ghost {
+            tail: 0,
+            cycle,
+            hits,
+        };
+        let solutions = Solutions::default().combine(&ghost(4, vec![2])).unwrap();
+        assert_eq!(
+            solutions.combine(&ghost(6, vec![0])),
+            Some(Solutions {
+                period: 12,
+                residues: vec![6]
+            })
+        );
+        let solutions = Solutions::default().combine(&ghost(4, vec![1])).unwrap();
+        assert_eq!(solutions.combine(&ghost(6, vec![0])).unwrap().residues, []);
+        let solutions = Solutions::default().combine(&ghost(3, vec![2])).unwrap();
+        assert_eq!(solutions.combine(&ghost(5, vec![3])).unwrap().residues, [8]);
+
+        // Dense hits are checked step by step within the common period
+        let solutions = Solutions::default()
+            .combine(&ghost(6, vec![0, 1, 2, 3, 4, 5]))
+            .unwrap();
+        assert_eq!(
+            solutions.combine(&ghost(4, vec![0, 2, 3])).unwrap(),
+            Solutions {
+                period: 12,
+                residues: vec![0, 2, 3, 4, 6, 7, 8, 10, 11]
+            }
+        );
+
+        // The common period of these cycles does not fit in a u128
+        let ghosts = [
+            ghost(usize::MAX, vec![0]),
+            ghost(usize::MAX - 1, vec![0]),
+            ghost(usize::MAX - 2, vec![0]),
+        ];
+        assert_eq!(first_common_hit(&ghosts), None);
     }
 }
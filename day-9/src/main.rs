@@ -2,12 +2,56 @@
 
 pub mod part1;
 pub mod part2;
+pub mod polynomial;
+
+use num_bigint::BigInt;
+use polynomial::Polynomial;
 
 const INPUT: &str = include_str!("./input.txt");
 
 fn main() {
-    let part_one_answer = part1::solve_part_one(INPUT);
-    println!("Part one: {part_one_answer:?}");
-    let part_two_answer = part2::solve_part_two(INPUT);
-    println!("Part two: {part_two_answer:?}");
+    // `day-9 - <part>` solves a single part streaming from stdin and
+    // `day-9 --extrapolate <steps>` fits a polynomial to every report and predicts
+    // the value `steps` ahead, or behind for negative `steps`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            let part_one_answer = part1::solve_part_one(INPUT);
+            println!("Part one: {part_one_answer:?}");
+            let part_two_answer = part2::solve_part_two(INPUT);
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["-", "1"] => {
+            let part_one_answer = part1::solve_part_one_streaming(std::io::stdin().lock());
+            println!("Part one: {part_one_answer:?}");
+        }
+        ["-", "2"] => {
+            let part_two_answer = part2::solve_part_two_streaming(std::io::stdin().lock());
+            println!("Part two: {part_two_answer:?}");
+        }
+        ["--extrapolate", steps] => {
+            let steps = steps.parse().expect("Steps should be a number.");
+            let mut total = BigInt::ZERO;
+            for line in INPUT.lines() {
+                let sequence: Vec<BigInt> = line
+                    .split_whitespace()
+                    .map(|num| num.parse().expect("Should be parsable."))
+                    .collect();
+                match Polynomial::fit(&sequence) {
+                    Ok(polynomial) => {
+                        let value = polynomial.extrapolate(steps);
+                        let degree = polynomial.degree().map_or(-1, |degree| degree as i64);
+                        println!("{line}: {polynomial} of degree {degree} gives {value}");
+                        total += value;
+                    }
+                    Err(err) => println!("{line}: {err}"),
+                }
+            }
+            println!("Total: {total}");
+        }
+        _ => {
+            eprintln!("Usage: day-9 [- <1|2> | --extrapolate <steps>]");
+            std::process::exit(2);
+        }
+    }
 }
@@ -1,12 +1,20 @@
 // Advent of Code - Day 9: Mirage Maintenance Part 1
 
+use std::io::BufRead;
+
 pub(crate) fn differences(sequence: &[i64]) -> Vec<i64> {
     sequence.windows(2).map(|w| w[1] - w[0]).collect()
 }
 
 pub(crate) fn solve_part_one(input: &str) -> i64 {
-    input
+    solve_part_one_streaming(input.as_bytes())
+}
+
+/// Solve part one report by report from any buffered reader, e.g. stdin
+pub(crate) fn solve_part_one_streaming<R: BufRead>(reader: R) -> i64 {
+    reader
         .lines()
+        .map(|line| line.expect("Input should be readable."))
         .map(|line| {
             let oasis_report = line
                 .split_whitespace()
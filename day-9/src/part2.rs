@@ -1,8 +1,16 @@
 // Advent of Code - Day 9: Mirage Maintenance Part 2
 
+use std::io::BufRead;
+
 pub(crate) fn solve_part_two(input: &str) -> i64 {
-    input
+    solve_part_two_streaming(input.as_bytes())
+}
+
+/// Solve part two report by report from any buffered reader, e.g. stdin
+pub(crate) fn solve_part_two_streaming<R: BufRead>(reader: R) -> i64 {
+    reader
         .lines()
+        .map(|line| line.expect("Input should be readable."))
         .map(|line| {
             let oasis_report = line
                 .split_whitespace()
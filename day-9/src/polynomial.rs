@@ -0,0 +1,199 @@
+// Advent of Code - Day 9: Mirage Maintenance Polynomial extrapolation
+
+use std::fmt;
+
+use num_bigint::{BigInt, BigUint};
+
+/// Polynomial of minimal degree through a sequence, in Newton forward form
+///
+/// The value at position `x`, with the sequence starting at `x = 0`, is the
+/// sum of `differences[k] * binomial(x, k)` over all `k`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Polynomial {
+    /// First value of every difference row, down to the last non-zero row
+    pub(crate) differences: Vec<BigInt>,
+    /// Length of the sequence the polynomial was fitted to
+    pub(crate) len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FitError {
+    /// The differences run out before a row of only zeros, so the sequence
+    /// is too short to determine a polynomial
+    NoZeroRow,
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitError::NoZeroRow => write!(f, "the differences never become all zeros"),
+        }
+    }
+}
+
+fn gcd(a: &BigInt, b: &BigInt) -> BigInt {
+    let (mut a, mut b) = (a.magnitude().clone(), b.magnitude().clone());
+    while b != BigUint::ZERO {
+        (a, b) = (b.clone(), a % b);
+    }
+    a.into()
+}
+
+impl Polynomial {
+    /// Fit the polynomial with Newton forward differences
+    pub(crate) fn fit(sequence: &[BigInt]) -> Result<Self, FitError> {
+        let mut row = sequence.to_vec();
+        let mut differences = Vec::new();
+        while !row.iter().all(|value| *value == BigInt::ZERO) {
+            differences.push(row[0].clone());
+            row = row.windows(2).map(|w| &w[1] - &w[0]).collect();
+            if row.is_empty() {
+                return Err(FitError::NoZeroRow);
+            }
+        }
+        Ok(Polynomial {
+            differences,
+            len: sequence.len(),
+        })
+    }
+
+    /// Degree of the polynomial, `None` for the zero polynomial
+    pub(crate) fn degree(&self) -> Option<usize> {
+        self.differences.len().checked_sub(1)
+    }
+
+    /// Value at position `x` of the sequence
+    pub(crate) fn value_at(&self, x: &BigInt) -> BigInt {
+        let mut binomial = BigInt::from(1);
+        let mut value = BigInt::ZERO;
+        for (k, difference) in self.differences.iter().enumerate() {
+            value += difference * &binomial;
+            // binomial(x, k + 1) = binomial(x, k) * (x - k) / (k + 1), always exact
+            binomial = binomial * (x - k) / (k + 1);
+        }
+        value
+    }
+
+    /// Value `steps` after the last element, or before the first one if `steps` is negative
+    pub(crate) fn extrapolate(&self, steps: i64) -> BigInt {
+        if steps >= 0 {
+            self.value_at(&(BigInt::from(self.len) - 1 + steps))
+        } else {
+            self.value_at(&BigInt::from(steps))
+        }
+    }
+
+    /// Coefficients in the monomial basis, lowest power first, as integer
+    /// numerators over a common positive denominator in lowest terms
+    pub(crate) fn coefficients(&self) -> (Vec<BigInt>, BigInt) {
+        let degree = self.differences.len();
+        let factorial = |n: usize| (1..=n).fold(BigInt::from(1), |acc, i| acc * i);
+        let mut numerators = vec![BigInt::ZERO; degree.max(1)];
+        // Falling factorial x (x - 1) ... (x - k + 1), lowest power first
+        let mut falling = vec![BigInt::from(1)];
+        for (k, difference) in self.differences.iter().enumerate() {
+            let scale = difference * factorial(degree.saturating_sub(1)) / factorial(k);
+            for (power, coefficient) in falling.iter().enumerate() {
+                numerators[power] += &scale * coefficient;
+            }
+            let mut next = vec![BigInt::ZERO; falling.len() + 1];
+            for (power, coefficient) in falling.iter().enumerate() {
+                next[power + 1] += coefficient;
+                next[power] -= coefficient * k;
+            }
+            falling = next;
+        }
+        let denominator = numerators
+            .iter()
+            .fold(factorial(degree.saturating_sub(1)), |acc, n| gcd(&acc, n));
+        let numerators = numerators.iter().map(|n| n / &denominator).collect();
+        (
+            numerators,
+            factorial(degree.saturating_sub(1)) / denominator,
+        )
+    }
+}
+
+impl fmt::Display for Polynomial {
+    /// Monomial form, e.g. `(x^2 + 3x + 2) / 2`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (numerators, denominator) = self.coefficients();
+        let mut terms = String::new();
+        for (power, coefficient) in numerators.iter().enumerate().rev() {
+            if *coefficient == BigInt::ZERO {
+                continue;
+            }
+            let magnitude = coefficient.magnitude();
+            let sign = match (terms.is_empty(), *coefficient < BigInt::ZERO) {
+                (true, true) => "-",
+                (true, false) => "",
+                (false, true) => " - ",
+                (false, false) => " + ",
+            };
+            let magnitude = match power {
+                0 => magnitude.to_string(),
+                _ if *magnitude == BigUint::from(1_u32) => String::new(),
+                _ => magnitude.to_string(),
+            };
+            let variable = match power {
+                0 => String::new(),
+                1 => "x".to_string(),
+                _ => format!("x^{power}"),
+            };
+            terms.push_str(&format!("{sign}{magnitude}{variable}"));
+        }
+        match (terms.is_empty(), denominator == BigInt::from(1)) {
+            (true, _) => write!(f, "0"),
+            (false, true) => write!(f, "{terms}"),
+            (false, false) => write!(f, "({terms}) / {denominator}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+
+    use crate::polynomial::{FitError, Polynomial};
+
+    fn fit(sequence: &str) -> Result<Polynomial, FitError> {
+        let sequence: Vec<BigInt> = sequence
+            .split_whitespace()
+            .map(|num| num.parse().unwrap())
+            .collect();
+        Polynomial::fit(&sequence)
+    }
+
+    #[test]
+    fn extrapolation() {
+        let polynomial = fit("10 13 16 21 30 45").unwrap();
+        assert_eq!(polynomial.degree(), Some(3));
+        assert_eq!(polynomial.extrapolate(1), BigInt::from(68));
+        assert_eq!(polynomial.extrapolate(-1), BigInt::from(5));
+        assert_eq!(polynomial.extrapolate(0), BigInt::from(45));
+
+        let polynomial = fit("0 3 6 9 12 15").unwrap();
+        assert_eq!(
+            polynomial.extrapolate(1_000_000_000_000_000_000),
+            "3000000000000000015".parse::<BigInt>().unwrap()
+        );
+    }
+
+    #[test]
+    fn coefficients() {
+        assert_eq!(
+            fit("1 3 6 10 15 21").unwrap().to_string(),
+            "(x^2 + 3x + 2) / 2"
+        );
+        assert_eq!(fit("0 3 6 9 12 15").unwrap().to_string(), "3x");
+        assert_eq!(fit("5 5 5").unwrap().to_string(), "5");
+        assert_eq!(fit("0 0").unwrap().to_string(), "0");
+        assert_eq!(fit("4 1 -2").unwrap().to_string(), "-3x + 4");
+    }
+
+    #[test]
+    fn no_zero_row() {
+        assert_eq!(fit("1 2 4 8"), Err(FitError::NoZeroRow));
+        assert_eq!(fit("7"), Err(FitError::NoZeroRow));
+    }
+}